@@ -0,0 +1,24 @@
+use std::fs::read_to_string;
+
+use aoc_2025::{DynDay, Part, default_input, registry};
+use parser_combinators::bench::bench;
+
+// Times each day's input grammar on its real input, skipping days whose input isn't present.
+fn day(day : &dyn DynDay) {
+    let filename = default_input(day.number());
+    let Ok(raw) = read_to_string(&filename) else {
+        println!("skipping day {}: {filename} not found", day.number());
+        return;
+    };
+    let raw_chars : Vec<char> = raw.chars().collect();
+    if let Err(e) = day.parse(Part::Easy, &filename, &raw_chars) {
+        panic!("{e}");
+    }
+    bench(&format!("day{} parse", day.number()), raw.len(), || day.parse(Part::Easy, &filename, &raw_chars).is_ok());
+}
+
+fn main() {
+    for d in registry().puzzles() {
+        day(d);
+    }
+}
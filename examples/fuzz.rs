@@ -0,0 +1,75 @@
+use std::{env, fs, path::PathBuf, process::exit};
+
+use aoc_2025::{DynDay, Part, registry};
+use parser_combinators::{Rng, fuzz::Fuzzer};
+
+// Fuzzes every day's parsers with mutated copies of its inputs, plus a few random inputs sampled
+// from its grammar. Parse errors are fine; panics are not. Crashing inputs are saved under
+// `target/fuzz/`.
+//
+//     cargo run --release --example fuzz -- [DAY...]
+//
+// `PC_FUZZ_RUNS` sets how many inputs to try per day (default 100000), `PC_SEED` the random seed.
+
+const SAMPLES : usize = 16;
+
+fn env_or(name : &str, default : u64) -> u64 {
+    env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn target(day : &dyn DynDay, bytes : &[u8]) -> bool {
+    let raw = String::from_utf8_lossy(bytes);
+    let raw_chars : Vec<char> = raw.chars().collect();
+    let easy = day.parse(Part::Easy, "fuzz", &raw_chars).is_ok();
+    let hard = day.parse(Part::Hard, "fuzz", &raw_chars).is_ok();
+    easy || hard
+}
+
+fn seeds(day : &dyn DynDay, rng : &mut Rng) -> Vec<Vec<u8>> {
+    let mut seeds : Vec<Vec<u8>> = ["", "_test"].iter()
+        .filter_map(|suffix| fs::read(format!("inputs/day{}{suffix}.txt", day.number())).ok())
+        .collect();
+    for size in 0..SAMPLES {
+        if let Some(chars) = day.sample(rng, size) {
+            seeds.push(chars.into_iter().collect::<String>().into_bytes());
+        }
+    }
+    seeds
+}
+
+// Returns whether the day survived.
+fn fuzz(dyn_day : &dyn DynDay, runs : usize, rng : &mut Rng) -> bool {
+    let day = dyn_day.number();
+    let mut fuzzer = Fuzzer::new(seeds(dyn_day, rng), rng.clone());
+    let Some(crash) = fuzzer.run(runs, |bytes| target(dyn_day, bytes)) else {
+        println!("day {day}: no crashes in {runs} runs (corpus of {} inputs)", fuzzer.corpus().len());
+        return true;
+    };
+
+    let dir = PathBuf::from("target/fuzz");
+    let path = dir.join(format!("day{day}-crash.txt"));
+    let saved = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, &crash.input));
+    println!("day {day}: crashed after {} runs: {}", crash.runs, crash.message);
+    println!("    input: {:?}", String::from_utf8_lossy(&crash.input));
+    match saved {
+        Ok(()) => println!("    saved to {}", path.display()),
+        Err(e) => println!("    couldn't save input: {e}"),
+    }
+    false
+}
+
+fn main() {
+    let runs = env_or("PC_FUZZ_RUNS", 100_000) as usize;
+    let mut rng = Rng::new(env_or("PC_SEED", 0x5eed));
+    let days : Vec<i32> = env::args().skip(1).map(|arg| arg.parse().expect("arguments are day numbers")).collect();
+
+    let mut survived = true;
+    for day in registry().puzzles() {
+        if days.is_empty() || days.contains(&day.number()) {
+            survived &= fuzz(day, runs, &mut rng);
+        }
+    }
+    if !survived {
+        exit(1);
+    }
+}
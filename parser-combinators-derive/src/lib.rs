@@ -0,0 +1,346 @@
+// `#[derive(Parse)]`, which implements parser-combinators' `FromParse`. It's written against the
+// bare `proc_macro` API, so this crate has no dependencies.
+//
+// A struct parses as its fields in order, and an enum as the first of its variants that parses.
+// Literal text between them comes from `#[parse(...)]` attributes:
+//
+// - `prefix = "..."` on a struct, enum, variant or field is text that comes before it. Unit
+//   variants without a prefix are written as their name.
+// - `sep = "..."` on a field is text that comes after it.
+//
+// Field types must implement `FromParse`, and `Clone` so that values can be printed.
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn derive_parse(input: TokenStream) -> TokenStream {
+    let code = match derive(input) {
+        Ok(code) => code,
+        Err(message) => format!(
+            "compile_error!({:?});",
+            format!("#[derive(Parse)]: {message}")
+        ),
+    };
+    code.parse().expect("generated code is valid")
+}
+
+// Attribute values are kept as the source text of their string literals, quotes and escapes
+// included, so they can be pasted straight into the generated code.
+#[derive(Default)]
+struct Attrs {
+    prefix: Option<String>,
+    sep: Option<String>,
+}
+
+struct Field {
+    name: Option<String>,
+    ty: String,
+    attrs: Attrs,
+}
+
+enum Fields {
+    Named(Vec<Field>),
+    Unnamed(Vec<Field>),
+    Unit,
+}
+
+struct Variant {
+    name: String,
+    attrs: Attrs,
+    fields: Fields,
+}
+
+fn derive(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut i = 0;
+    let attrs = parse_attrs(&tokens, &mut i, &["prefix"])?;
+    skip_visibility(&tokens, &mut i);
+    let kind = ident(&tokens, &mut i)?;
+    let name = ident(&tokens, &mut i)?;
+    if matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        return Err("generic types aren't supported".to_string());
+    }
+
+    let parser = match kind.as_str() {
+        "struct" => {
+            let fields = match tokens.get(i) {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                    Fields::Named(parse_fields(g.stream(), true)?)
+                }
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                    Fields::Unnamed(parse_fields(g.stream(), false)?)
+                }
+                Some(TokenTree::Punct(p)) if p.as_char() == ';' => Fields::Unit,
+                _ => return Err("where clauses aren't supported".to_string()),
+            };
+            fields_parser("Self", &fields, None, false)
+        }
+        "enum" => {
+            let Some(TokenTree::Group(body)) = tokens.get(i) else {
+                return Err("expected enum body".to_string());
+            };
+            let variants = parse_variants(body.stream())?;
+            let Some((last, rest)) = variants.split_last() else {
+                return Err("enums without variants can't be parsed".to_string());
+            };
+            // Every variant but the last backtracks, so that variants may share more than their
+            // first character.
+            let variant_parser = |v: &Variant, backtrack| {
+                let constructor = format!("Self::{}", v.name);
+                let prefix = match (&v.attrs.prefix, &v.fields) {
+                    (None, Fields::Unit) => Some(format!("{:?}", v.name)),
+                    (prefix, _) => prefix.clone(),
+                };
+                let p = fields_parser(&constructor, &v.fields, prefix.as_deref(), true);
+                if backtrack {
+                    format!("::parser_combinators::Parser::<char>::backtrack({p})")
+                } else {
+                    p
+                }
+            };
+            rest.iter().rev().fold(variant_parser(last, false), |p, v| {
+                format!(
+                    "::parser_combinators::Parser::<char>::disj({}, {p})",
+                    variant_parser(v, true)
+                )
+            })
+        }
+        _ => return Err("only structs and enums can be derived".to_string()),
+    };
+    let parser = with_prefix(parser, attrs.prefix.as_deref());
+
+    Ok(format!(
+        "impl ::parser_combinators::FromParse for {name} {{
+            fn parser<'a>() -> impl ::parser_combinators::Syntax<'a, char, O = Self> {{
+                ::parser_combinators::Parser::<char>::named({parser}, {name:?})
+            }}
+        }}"
+    ))
+}
+
+// A parser for `constructor` with `fields`, printing values that match `constructor` (which must
+// be all of them unless `refutable`).
+fn fields_parser(
+    constructor: &str,
+    fields: &Fields,
+    prefix: Option<&str>,
+    refutable: bool,
+) -> String {
+    let (fields, pattern) = match fields {
+        Fields::Named(fields) => {
+            let bindings: Vec<String> = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| format!("{}: f{i}", f.name.as_deref().unwrap_or_default()))
+                .collect();
+            (
+                &fields[..],
+                format!("{constructor} {{ {} }}", bindings.join(", ")),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<String> = (0..fields.len()).map(|i| format!("f{i}")).collect();
+            (
+                &fields[..],
+                format!("{constructor}({})", bindings.join(", ")),
+            )
+        }
+        Fields::Unit => (&[][..], constructor.to_string()),
+    };
+
+    let parsers: Vec<String> = fields
+        .iter()
+        .map(|f| {
+            let p = format!("<{} as ::parser_combinators::FromParse>::parser()", f.ty);
+            let p = with_prefix(p, f.attrs.prefix.as_deref());
+            match &f.attrs.sep {
+                Some(sep) => format!(
+                    "::parser_combinators::Parser::<char>::seql({p}, ::parser_combinators::literal({sep}))"
+                ),
+                None => p,
+            }
+        })
+        .collect();
+    let parser = if parsers.is_empty() {
+        "::parser_combinators::pure(())".to_string()
+    } else {
+        nest(&parsers)
+    };
+    let bindings: Vec<String> = (0..fields.len()).map(|i| format!("f{i}")).collect();
+    let clones: Vec<String> = bindings.iter().map(|b| format!("{b}.clone()")).collect();
+    let (bindings, clones) = (nest(&bindings), nest(&clones));
+
+    let print = if refutable {
+        format!(
+            "match v {{ {pattern} => Some({clones}), #[allow(unreachable_patterns)] _ => None }}"
+        )
+    } else {
+        format!("{{ let {pattern} = v; Some({clones}) }}")
+    };
+    let parser = format!(
+        "::parser_combinators::Parser::<char>::iso({parser}, |{bindings}| {pattern}, |v: &Self| {print})"
+    );
+    with_prefix(parser, prefix)
+}
+
+fn with_prefix(parser: String, prefix: Option<&str>) -> String {
+    match prefix {
+        Some(prefix) => format!(
+            "::parser_combinators::Parser::<char>::seqr(::parser_combinators::literal({prefix}), {parser})"
+        ),
+        None => parser,
+    }
+}
+
+// `(a, (b, c))` from `[a, b, c]`, the shape of nested pair parsers.
+fn nest(items: &[String]) -> String {
+    match items {
+        [] => "()".to_string(),
+        [item] => item.clone(),
+        [first, rest @ ..] => format!("({first}, {})", nest(rest)),
+    }
+}
+
+fn ident(tokens: &[TokenTree], i: &mut usize) -> Result<String, String> {
+    match tokens.get(*i) {
+        Some(TokenTree::Ident(ident)) => {
+            *i += 1;
+            Ok(ident.to_string())
+        }
+        Some(token) => Err(format!("expected identifier, found `{token}`")),
+        None => Err("expected identifier".to_string()),
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == c)
+}
+
+fn skip_visibility(tokens: &[TokenTree], i: &mut usize) {
+    if matches!(tokens.get(*i), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        *i += 1;
+        if matches!(tokens.get(*i), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+        {
+            *i += 1;
+        }
+    }
+}
+
+// Reads the attributes at `tokens[*i..]`, collecting the `#[parse(...)]` ones and skipping the
+// rest (doc comments, other derives' attributes).
+fn parse_attrs(tokens: &[TokenTree], i: &mut usize, allowed: &[&str]) -> Result<Attrs, String> {
+    let mut attrs = Attrs::default();
+    while is_punct(tokens.get(*i), '#') {
+        let Some(TokenTree::Group(attr)) = tokens.get(*i + 1) else {
+            return Err("expected attribute".to_string());
+        };
+        *i += 2;
+        let attr: Vec<TokenTree> = attr.stream().into_iter().collect();
+        let [TokenTree::Ident(name), TokenTree::Group(args)] = &attr[..] else {
+            continue;
+        };
+        if name.to_string() != "parse" {
+            continue;
+        }
+        for arg in split_commas(args.stream()) {
+            let [
+                TokenTree::Ident(key),
+                TokenTree::Punct(eq),
+                TokenTree::Literal(value),
+            ] = &arg[..]
+            else {
+                return Err("expected `key = \"value\"` in #[parse(...)]".to_string());
+            };
+            let (key, value) = (key.to_string(), value.to_string());
+            if eq.as_char() != '=' || !(value.starts_with('"') || value.starts_with('r')) {
+                return Err(format!("`{key}` must be a string literal"));
+            }
+            if !allowed.contains(&key.as_str()) {
+                return Err(format!(
+                    "`{key}` isn't allowed here (expected one of {allowed:?})"
+                ));
+            }
+            match key.as_str() {
+                "prefix" => attrs.prefix = Some(value),
+                _ => attrs.sep = Some(value),
+            }
+        }
+    }
+    Ok(attrs)
+}
+
+// Splits at top-level commas. Commas inside groups are already nested, but those inside generic
+// arguments (`HashMap<K, V>`) aren't, so angle brackets are counted.
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in stream {
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                ',' if depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            arrow = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+        parts.last_mut().expect("parts is never empty").push(token);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn parse_fields(stream: TokenStream, named: bool) -> Result<Vec<Field>, String> {
+    split_commas(stream)
+        .into_iter()
+        .map(|tokens| {
+            let mut i = 0;
+            let attrs = parse_attrs(&tokens, &mut i, &["prefix", "sep"])?;
+            skip_visibility(&tokens, &mut i);
+            let name = if named {
+                let name = ident(&tokens, &mut i)?;
+                if !is_punct(tokens.get(i), ':') {
+                    return Err(format!("expected `:` after field `{name}`"));
+                }
+                i += 1;
+                Some(name)
+            } else {
+                None
+            };
+            let ty = TokenStream::from_iter(tokens[i..].iter().cloned()).to_string();
+            Ok(Field { name, ty, attrs })
+        })
+        .collect()
+}
+
+fn parse_variants(stream: TokenStream) -> Result<Vec<Variant>, String> {
+    split_commas(stream)
+        .into_iter()
+        .map(|tokens| {
+            let mut i = 0;
+            let attrs = parse_attrs(&tokens, &mut i, &["prefix"])?;
+            let name = ident(&tokens, &mut i)?;
+            let fields = match tokens.get(i) {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                    Fields::Named(parse_fields(g.stream(), true)?)
+                }
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                    Fields::Unnamed(parse_fields(g.stream(), false)?)
+                }
+                // Either nothing or an explicit discriminant.
+                _ => Fields::Unit,
+            };
+            Ok(Variant {
+                name,
+                attrs,
+                fields,
+            })
+        })
+        .collect()
+}
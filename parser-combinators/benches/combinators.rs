@@ -0,0 +1,96 @@
+use parser_combinators::bench::bench;
+use parser_combinators::*;
+
+const INPUT_BYTES: usize = 1 << 20;
+
+fn chr<'a>(c: char) -> impl Parser<'a, char, O = &'a char> {
+    read().satisfy(move |x: &&char| **x == c)
+}
+
+fn digit<'a>() -> impl Parser<'a, char, O = &'a char> {
+    read().satisfy(|c: &&char| c.is_ascii_digit())
+}
+
+fn nat<'a>() -> impl Parser<'a, char, O = u64> {
+    digit().many1().map(|ds| {
+        ds.into_iter().fold(0u64, |n, d| {
+            n.wrapping_mul(10).wrapping_add(*d as u64 - '0' as u64)
+        })
+    })
+}
+
+// Random numbers of up to 12 digits, separated by `sep`, totalling about `INPUT_BYTES`.
+fn numbers(rng: &mut Rng, sep: char) -> String {
+    let mut text = String::new();
+    while text.len() < INPUT_BYTES {
+        let n = rng.next_u64() % 1_000_000_000_000;
+        text.push_str(&n.to_string());
+        text.push(sep);
+    }
+    text
+}
+
+fn letters(rng: &mut Rng, alphabet: &[char]) -> String {
+    (0..INPUT_BYTES)
+        .map(|_| alphabet[rng.below(alphabet.len())])
+        .collect()
+}
+
+fn run<O>(name: &str, text: &str, parser: impl for<'a> Fn(&'a [char]) -> ParseResult<'a, char, O>) {
+    let chars: Vec<char> = text.chars().collect();
+    match parser(&chars) {
+        Ok((_, [])) => {}
+        _ => panic!("benchmark {name:?} doesn't parse its whole input"),
+    }
+    bench(name, text.len(), || parser(&chars).is_ok());
+}
+
+fn main() {
+    let mut rng = Rng::new(0xbe_4c4);
+    let digits: String = (0..INPUT_BYTES)
+        .map(|_| char::from(b'0' + rng.below(10) as u8))
+        .collect();
+    let lines = numbers(&mut rng, '\n');
+    let commas = numbers(&mut rng, ',');
+    let eight: Vec<char> = "abcdefgh".chars().collect();
+    let choices = letters(&mut rng, &eight);
+
+    run("many1 digit", &digits, |t| {
+        digit().many1().map(|ds| ds.len()).parse(t)
+    });
+    run("nat then newline, many", &lines, |t| {
+        nat().seql(chr('\n')).many().parse(t)
+    });
+    run("sep_end_by1 nat, newline", &lines, |t| {
+        nat().sep_end_by1(chr('\n')).parse(t)
+    });
+    run("sep_end_by1 nat, comma", &commas, |t| {
+        nat().sep_end_by1(chr(',')).parse(t)
+    });
+    run("parse_iter nat, newline", &lines, |t| {
+        let res: Result<Vec<_>, _> = parse_iter(nat(), chr('\n'), t).collect();
+        res.map(|ns| (ns, &t[t.len()..]))
+    });
+    run("disj chain of 8 chars, many", &choices, |t| {
+        chr('a')
+            .disj(chr('b'))
+            .disj(chr('c'))
+            .disj(chr('d'))
+            .disj(chr('e'))
+            .disj(chr('f'))
+            .disj(chr('g'))
+            .disj(chr('h'))
+            .many()
+            .map(|cs| cs.len())
+            .parse(t)
+    });
+    run("nested disj of 8 chars, many", &choices, |t| {
+        chr('a')
+            .disj(chr('b').disj(
+                chr('c').disj(chr('d').disj(chr('e').disj(chr('f').disj(chr('g').disj(chr('h')))))),
+            ))
+            .many()
+            .map(|cs| cs.len())
+            .parse(t)
+    });
+}
@@ -0,0 +1,94 @@
+// A small benchmark runner for measuring parser throughput without a dependency. Each benchmark
+// is warmed up, then timed in batches for about `PC_BENCH_MS` milliseconds (default 1000), and
+// reported as the median time per iteration and MB/s over the input. Command line arguments that
+// aren't flags filter benchmarks by name, as with `cargo bench -- sep_end_by`.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const DEFAULT_MILLIS: u64 = 1000;
+const SAMPLES: u32 = 50;
+
+pub struct Measurement {
+    pub name: String,
+    pub bytes: usize,
+    pub iterations: u64,
+    pub median: Duration,
+}
+
+impl Measurement {
+    // Throughput in (decimal) megabytes per second.
+    pub fn throughput(&self) -> f64 {
+        self.bytes as f64 / self.median.as_secs_f64() / 1e6
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<40} {:>12.3?}/iter {:>10.2} MB/s ({} iterations)",
+            self.name,
+            self.median,
+            self.throughput(),
+            self.iterations
+        )
+    }
+}
+
+fn budget() -> Duration {
+    let millis = std::env::var("PC_BENCH_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MILLIS);
+    Duration::from_millis(millis)
+}
+
+fn selected(name: &str) -> bool {
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+    filters.is_empty() || filters.iter().any(|filter| name.contains(filter.as_str()))
+}
+
+// Times `f`, which processes an input of `bytes` bytes, and prints the result. Returns `None` if
+// the benchmark was filtered out.
+pub fn bench<R>(name: &str, bytes: usize, mut f: impl FnMut() -> R) -> Option<Measurement> {
+    if !selected(name) {
+        return None;
+    }
+    let budget = budget();
+
+    // Warm up for a tenth of the budget, which also estimates how many iterations fit in a sample.
+    let start = Instant::now();
+    let mut warmup = 0u32;
+    while warmup == 0 || start.elapsed() < budget / 10 {
+        black_box(f());
+        warmup += 1;
+    }
+    let per_iteration = start.elapsed() / warmup;
+    let batch = (budget / SAMPLES).as_nanos() / per_iteration.as_nanos().max(1);
+    let batch = batch.clamp(1, u32::MAX as u128) as u32;
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < SAMPLES as usize && (samples.is_empty() || start.elapsed() < budget) {
+        let sample = Instant::now();
+        for _ in 0..batch {
+            black_box(f());
+        }
+        samples.push(sample.elapsed() / batch);
+    }
+    samples.sort();
+
+    let measurement = Measurement {
+        name: name.to_string(),
+        bytes,
+        iterations: samples.len() as u64 * batch as u64,
+        median: samples[samples.len() / 2],
+    };
+    println!("{measurement}");
+    Some(measurement)
+}
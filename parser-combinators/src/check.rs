@@ -0,0 +1,183 @@
+// A minimal property-testing harness: random inputs from a `Strategy`, and on failure repeated
+// shrinking to a smallest counterexample. Set `PC_SEED` to reproduce a run and `PC_CASES` to
+// change how many inputs each property is checked on.
+
+use std::fmt::Debug;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use crate::Rng;
+
+const DEFAULT_CASES: usize = 256;
+const DEFAULT_SEED: u64 = 0x5eed;
+const MAX_SIZE: usize = 32;
+
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value;
+
+    // Simpler variants of `value`, most aggressive first.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+// Strings over `alphabet`, shrunk by dropping chunks and single chars and then by replacing chars
+// with the first letter of the alphabet.
+pub struct Strings(pub Vec<char>);
+
+pub fn strings(alphabet: &str) -> Strings {
+    Strings(alphabet.chars().collect())
+}
+
+impl Strategy for Strings {
+    type Value = Vec<char>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<char> {
+        let len = rng.below(size + 1);
+        (0..len).map(|_| self.0[rng.below(self.0.len())]).collect()
+    }
+
+    fn shrink(&self, value: &Vec<char>) -> Vec<Vec<char>> {
+        let mut res = Vec::new();
+        let mut chunk = value.len() / 2;
+        while chunk > 0 {
+            for start in (0..value.len()).step_by(chunk) {
+                let mut smaller = value.clone();
+                smaller.drain(start..(start + chunk).min(value.len()));
+                res.push(smaller);
+            }
+            chunk /= 2;
+        }
+        for (i, c) in value.iter().enumerate() {
+            if *c != self.0[0] {
+                let mut simpler = value.clone();
+                simpler[i] = self.0[0];
+                res.push(simpler);
+            }
+        }
+        res
+    }
+}
+
+// Numbers in `0..=max`, shrunk towards 0.
+pub struct Naturals(pub u64);
+
+pub fn naturals(max: u64) -> Naturals {
+    Naturals(max)
+}
+
+impl Strategy for Naturals {
+    type Value = u64;
+
+    fn generate(&self, rng: &mut Rng, _: usize) -> u64 {
+        match self.0.checked_add(1) {
+            Some(n) => rng.next_u64() % n,
+            None => rng.next_u64(),
+        }
+    }
+
+    fn shrink(&self, value: &u64) -> Vec<u64> {
+        let mut res = Vec::new();
+        let mut diff = *value;
+        while diff > 0 {
+            res.push(value - diff);
+            diff /= 2;
+        }
+        res
+    }
+}
+
+// Vectors of up to `size` elements, shrunk by removing elements and then by shrinking them.
+pub struct Vecs<S>(pub S);
+
+pub fn vecs<S: Strategy>(item: S) -> Vecs<S> {
+    Vecs(item)
+}
+
+impl<S: Strategy> Strategy for Vecs<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+        let len = rng.below(size + 1);
+        (0..len).map(|_| self.0.generate(rng, size / 2)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut res = Vec::new();
+        for i in 0..value.len() {
+            let mut smaller = value.clone();
+            smaller.remove(i);
+            res.push(smaller);
+        }
+        for (i, item) in value.iter().enumerate() {
+            for simpler in self.0.shrink(item) {
+                let mut smaller = value.clone();
+                smaller[i] = simpler;
+                res.push(smaller);
+            }
+        }
+        res
+    }
+}
+
+impl<S1: Strategy, S2: Strategy> Strategy for (S1, S2) {
+    type Value = (S1::Value, S2::Value);
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Value {
+        (self.0.generate(rng, size), self.1.generate(rng, size))
+    }
+
+    fn shrink(&self, (value1, value2): &Self::Value) -> Vec<Self::Value> {
+        let firsts = self
+            .0
+            .shrink(value1)
+            .into_iter()
+            .map(|v| (v, value2.clone()));
+        let seconds = self
+            .1
+            .shrink(value2)
+            .into_iter()
+            .map(|v| (value1.clone(), v));
+        firsts.chain(seconds).collect()
+    }
+}
+
+fn env_or<N: std::str::FromStr>(name: &str, default: N) -> N {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn holds<V>(property: &impl Fn(&V) -> bool, value: &V) -> bool {
+    catch_unwind(AssertUnwindSafe(|| property(value))).unwrap_or(false)
+}
+
+// Checks `property` on random values from `strategy` of increasing size, panicking with the
+// smallest failing value found. A panic inside `property` counts as a failure.
+pub fn check<S: Strategy>(name: &str, strategy: S, property: impl Fn(&S::Value) -> bool) {
+    let seed = env_or("PC_SEED", DEFAULT_SEED);
+    let cases = env_or("PC_CASES", DEFAULT_CASES);
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let size = case * MAX_SIZE / cases.max(1);
+        let value = strategy.generate(&mut rng, size);
+        if holds(&property, &value) {
+            continue;
+        }
+
+        let mut smallest = value.clone();
+        let mut steps = 0;
+        while let Some(smaller) = strategy
+            .shrink(&smallest)
+            .into_iter()
+            .find(|candidate| !holds(&property, candidate))
+        {
+            smallest = smaller;
+            steps += 1;
+        }
+        panic!(
+            "property {name:?} failed (seed {seed}, case {case}) for {smallest:?} \
+             (shrunk in {steps} steps from {value:?})"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Type-directed parsing: `FromParse` gives a type its canonical syntax, so that parsers for
+// compound types can be put together from the types of their parts. `#[derive(Parse)]`
+// implements it for structs and enums, using the literals in their `#[parse(...)]` attributes.
+//
+// The syntax for standard types follows the usual layout of puzzle inputs, from the outside in:
+// tuples are sections separated by blank lines, `Vec`s have one item per line, and arrays are
+// items separated by spaces on a single line.
+
+use std::str::FromStr;
+
+use crate::{ErrorType, Gen, Grammar, ParseError, ParseResult, Parser, Syntax, read, sections};
+
+pub trait FromParse: Sized {
+    fn parser<'a>() -> impl Syntax<'a, char, O = Self>;
+}
+
+pub struct Literal(Vec<char>);
+
+impl<'a> Parser<'a, char> for Literal {
+    type O = ();
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        if tokens.starts_with(&self.0) {
+            Ok(((), &tokens[self.0.len()..]))
+        } else {
+            Err(ParseError {
+                typ: ErrorType::Msg(format!("expected {:?}", String::from_iter(&self.0))),
+                rest: tokens,
+            })
+        }
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Literal(self.0.iter().collect())
+    }
+
+    fn generate(&self, _: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        out.extend(&self.0);
+        Some(())
+    }
+}
+
+impl Syntax<'_, char> for Literal {
+    fn print(&self, _: &(), out: &mut Vec<char>) -> bool {
+        out.extend(&self.0);
+        true
+    }
+}
+
+pub fn literal(s: &str) -> Literal {
+    Literal(s.chars().collect())
+}
+
+// Digits with an optional minus sign. Numbers that don't fit in `N` (including negative numbers
+// for unsigned `N`) fail to parse.
+pub fn integer<'a, N: FromStr + ToString>() -> impl Syntax<'a, char, O = N> {
+    let digits = read().satisfy(|c: &&char| c.is_ascii_digit()).many1();
+    (literal("-").optional(), digits)
+        .map(|(minus, ds)| {
+            let sign = minus.map(|()| &'-');
+            sign.into_iter().chain(ds).collect::<String>()
+        })
+        .try_map(|s| s.parse::<N>().ok())
+        .print_with(|n: &N| Some(n.to_string().chars().collect()))
+        .named("integer")
+}
+
+macro_rules! impl_from_parse_integer {
+    ($($n:ty),*) => {
+        $(
+            impl FromParse for $n {
+                fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+                    integer()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_parse_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+// Any character but a newline, which would split lines.
+impl FromParse for char {
+    fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+        read()
+            .satisfy(|c: &&char| **c != '\n')
+            .map_print(|c: &char| *c, |c: &char| Some(vec![*c]))
+    }
+}
+
+impl FromParse for bool {
+    fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+        literal("true")
+            .map_const(true)
+            .disj(literal("false").map_const(false))
+            .named("bool")
+    }
+}
+
+// Empty `Vec`s are allowed, as an empty line or section, so that they round-trip like any other.
+impl<T: FromParse> FromParse for Vec<T> {
+    fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+        T::parser().sep_by(literal("\n"))
+    }
+}
+
+impl<T: FromParse + Clone, const N: usize> FromParse for [T; N] {
+    fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+        let spaces = literal(" ").many1().print_as([' ']);
+        T::parser()
+            .sep_by(spaces)
+            .satisfy(|items| items.len() == N)
+            .iso(
+                |items| {
+                    items
+                        .try_into()
+                        .unwrap_or_else(|_| unreachable!("checked by satisfy"))
+                },
+                |items: &[T; N]| Some(items.to_vec()),
+            )
+    }
+}
+
+macro_rules! impl_from_parse_tuple {
+    ($($T:ident),*) => {
+        impl<$($T: FromParse),*> FromParse for ($($T,)*) {
+            fn parser<'a>() -> impl Syntax<'a, char, O = Self> {
+                sections(($($T::parser(),)*))
+            }
+        }
+    };
+}
+
+impl_from_parse_tuple!(A);
+impl_from_parse_tuple!(A, B);
+impl_from_parse_tuple!(A, B, C);
+impl_from_parse_tuple!(A, B, C, D);
+impl_from_parse_tuple!(A, B, C, D, E);
+impl_from_parse_tuple!(A, B, C, D, E, F);
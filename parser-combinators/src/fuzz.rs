@@ -0,0 +1,143 @@
+// A random-mutation fuzzer in the style of libFuzzer, minus the coverage instrumentation: inputs
+// are mutated from a corpus of seeds and fed to a target, and the first input that makes the
+// target panic is reported. Inputs the target accepts join the corpus, which stands in for
+// coverage feedback well enough for parsers, where accepted inputs are the interesting ones.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe, catch_unwind};
+
+use crate::Rng;
+
+const MAX_CORPUS: usize = 1024;
+const MAX_MUTATIONS: usize = 8;
+
+#[derive(Debug)]
+pub struct Crash {
+    pub input: Vec<u8>,
+    pub message: String,
+    // How many inputs were tried before this one.
+    pub runs: usize,
+}
+
+pub struct Fuzzer {
+    corpus: Vec<Vec<u8>>,
+    rng: Rng,
+    pub max_len: usize,
+}
+
+impl Fuzzer {
+    pub fn new(seeds: impl IntoIterator<Item = Vec<u8>>, rng: Rng) -> Self {
+        let mut corpus: Vec<Vec<u8>> = seeds.into_iter().collect();
+        if corpus.is_empty() {
+            corpus.push(Vec::new());
+        }
+        let max_len = corpus.iter().map(Vec::len).max().unwrap_or(0).max(64) * 2;
+        Fuzzer {
+            corpus,
+            rng,
+            max_len,
+        }
+    }
+
+    pub fn corpus(&self) -> &[Vec<u8>] {
+        &self.corpus
+    }
+
+    // Runs `target` on the seeds and then on `runs` mutated inputs, stopping at the first panic.
+    // `target` returns whether it accepted the input, in which case the input is kept for further
+    // mutation.
+    pub fn run(&mut self, runs: usize, target: impl Fn(&[u8]) -> bool) -> Option<Crash> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let res = self.run_quietly(runs, target);
+        panic::set_hook(hook);
+        res
+    }
+
+    fn run_quietly(&mut self, runs: usize, target: impl Fn(&[u8]) -> bool) -> Option<Crash> {
+        let seeds = self.corpus.len();
+        for (i, seed) in self.corpus.clone().into_iter().enumerate() {
+            if let Err(message) = try_target(&target, &seed) {
+                return Some(Crash {
+                    input: seed,
+                    message,
+                    runs: i,
+                });
+            }
+        }
+        for i in 0..runs {
+            let input = self.mutant();
+            match try_target(&target, &input) {
+                Ok(true) if self.corpus.len() < MAX_CORPUS => self.corpus.push(input),
+                Ok(_) => {}
+                Err(message) => {
+                    return Some(Crash {
+                        input,
+                        message,
+                        runs: seeds + i,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    // A corpus entry with a few random mutations applied.
+    fn mutant(&mut self) -> Vec<u8> {
+        let mut input = self.corpus[self.rng.below(self.corpus.len())].clone();
+        for _ in 0..1 + self.rng.below(MAX_MUTATIONS) {
+            self.mutate(&mut input);
+        }
+        input.truncate(self.max_len);
+        input
+    }
+
+    fn mutate(&mut self, input: &mut Vec<u8>) {
+        let rng = &mut self.rng;
+        let at = rng.below(input.len() + 1);
+        match rng.below(8) {
+            0 if at < input.len() => input[at] ^= 1 << rng.below(8),
+            1 if at < input.len() => input[at] = rng.next_u64() as u8,
+            2 => input.insert(at, rng.next_u64() as u8),
+            // A byte that's already meaningful somewhere in the corpus, such as a separator.
+            3 => {
+                let other = &self.corpus[rng.below(self.corpus.len())];
+                if !other.is_empty() {
+                    input.insert(at, other[rng.below(other.len())]);
+                }
+            }
+            4 => {
+                let end = (at + 1 + rng.below(16)).min(input.len());
+                input.drain(at.min(end)..end);
+            }
+            // Repeating a byte many times makes long numbers and runs of separators.
+            5 if at < input.len() => {
+                let run = vec![input[at]; 1 + rng.below(32)];
+                input.splice(at..at, run);
+            }
+            6 => {
+                let other = &self.corpus[rng.below(self.corpus.len())];
+                let start = rng.below(other.len() + 1);
+                let end = (start + rng.below(64)).min(other.len());
+                input.splice(at..at, other[start..end].iter().copied());
+            }
+            7 => input.truncate(at),
+            _ => {}
+        }
+    }
+}
+
+// The message a panic was started with, from the payload `catch_unwind` returns.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+fn try_target(target: &impl Fn(&[u8]) -> bool, input: &[u8]) -> Result<bool, String> {
+    catch_unwind(AssertUnwindSafe(|| target(input))).map_err(|payload| panic_message(&*payload))
+}
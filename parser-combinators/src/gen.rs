@@ -0,0 +1,165 @@
+// Random generation of inputs that a parser accepts, driven by the same combinators that parse
+// them. `Parser::generate` produces both the tokens and the value they should parse to, so that
+// `satisfy` can reject candidates the same way parsing would. Hand-written parsers (closures)
+// can't be generated from.
+
+use crate::Parser;
+
+pub(crate) const SATISFY_ATTEMPTS: usize = 1024;
+
+// Printable ASCII plus newline, the alphabet `read()` draws from when sampling `char` parsers.
+pub static ASCII: [char; 96] = {
+    let mut chars = ['\n'; 96];
+    let mut i = 1;
+    while i < 96 {
+        chars[i] = (b' ' + i as u8 - 1) as char;
+        i += 1;
+    }
+    chars
+};
+
+// A small SplitMix64 generator, so sampling needs no dependencies and is reproducible from a seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Rng(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `0..n`, or 0 if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next_u64() % n as u64) as usize
+        }
+    }
+
+    pub fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn shuffle<X>(&mut self, xs: &mut [X]) {
+        for i in (1..xs.len()).rev() {
+            xs.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+pub struct Gen<'a, T> {
+    pub rng: Rng,
+    pub alphabet: &'a [T],
+    // Upper bound on the number of repetitions, halved for each level of nesting.
+    pub size: usize,
+}
+
+impl<T> Gen<'_, T> {
+    // A repetition count for something that must occur at least `min` times.
+    pub fn count(&mut self, min: usize) -> usize {
+        min + self.rng.below(self.size.saturating_sub(min) + 1)
+    }
+
+    // Runs `f` with a smaller size, for generating the items of a repetition.
+    pub fn nested<X>(&mut self, f: impl FnOnce(&mut Self) -> X) -> X {
+        let size = self.size;
+        self.size = size / 2;
+        let res = f(self);
+        self.size = size;
+        res
+    }
+}
+
+// Generates a random input of roughly `size` that `parser` should accept, drawing single tokens
+// from `alphabet`. Returns `None` if the parser is (partly) hand-written or a `satisfy` kept
+// rejecting candidates.
+pub fn sample_from<'a, T: Clone + 'a, P: Parser<'a, T>>(
+    parser: &P,
+    alphabet: &'a [T],
+    rng: &mut Rng,
+    size: usize,
+) -> Option<Vec<T>> {
+    let mut g = Gen {
+        rng: rng.clone(),
+        alphabet,
+        size,
+    };
+    let mut out = Vec::new();
+    let res = parser.generate(&mut g, &mut out);
+    *rng = g.rng;
+    res.map(|_| out)
+}
+
+pub fn sample<'a, P: Parser<'a, char>>(parser: &P, rng: &mut Rng, size: usize) -> Option<Vec<char>> {
+    sample_from(parser, &ASCII, rng, size)
+}
+
+pub(crate) fn generate_many<'a, T, P>(
+    p: &P,
+    n: usize,
+    g: &mut Gen<'a, T>,
+    out: &mut Vec<T>,
+) -> Option<Vec<P::O>>
+where
+    T: Clone + 'a,
+    P: Parser<'a, T>,
+{
+    g.nested(|g| (0..n).map(|_| p.generate(g, out)).collect())
+}
+
+// Generates `n` `p`s separated by `sep`, and followed by one more `sep` if `trailing`.
+pub(crate) fn generate_sep<'a, T, P, PS>(
+    p: &P,
+    sep: &PS,
+    n: usize,
+    trailing: bool,
+    g: &mut Gen<'a, T>,
+    out: &mut Vec<T>,
+) -> Option<Vec<P::O>>
+where
+    T: Clone + 'a,
+    P: Parser<'a, T>,
+    PS: Parser<'a, T>,
+{
+    g.nested(|g| {
+        let mut res = Vec::new();
+        for i in 0..n {
+            if i > 0 {
+                sep.generate(g, out)?;
+            }
+            res.push(p.generate(g, out)?);
+        }
+        if trailing && n > 0 {
+            sep.generate(g, out)?;
+        }
+        Some(res)
+    })
+}
+
+// Runs `f`, throwing away any partial output if it fails.
+pub(crate) fn attempt<T, O>(
+    out: &mut Vec<T>,
+    f: impl FnOnce(&mut Vec<T>) -> Option<O>,
+) -> Option<O> {
+    let len = out.len();
+    let res = f(out);
+    if res.is_none() {
+        out.truncate(len);
+    }
+    res
+}
@@ -0,0 +1,523 @@
+// A description of the language a parser accepts, built by `Parser::grammar`, which can be
+// exported as EBNF or drawn as a railroad diagram. Hand-written parsers (closures) show up as
+// `Opaque` unless wrapped with `named`.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grammar {
+    // Any single token
+    Any,
+    Literal(String),
+    // Tokens matching the inner grammar that also pass a predicate
+    Predicate(Box<Grammar>),
+    // Something not expressible as a grammar, described in words
+    Special(String),
+    Rule(String, Box<Grammar>),
+    Seq(Vec<Grammar>),
+    Alt(Vec<Grammar>),
+    // Each alternative exactly once, in any order
+    Permutation(Vec<Grammar>),
+    Optional(Box<Grammar>),
+    // One or more `item`s, separated by `sep` if there is one
+    Repeat {
+        item: Box<Grammar>,
+        sep: Option<Box<Grammar>>,
+    },
+    Empty,
+    Fail,
+    Opaque,
+}
+
+impl Grammar {
+    pub fn seq(items: impl IntoIterator<Item = Grammar>) -> Grammar {
+        let mut flat = Vec::new();
+        for item in items {
+            match item {
+                Grammar::Seq(items) => flat.extend(items),
+                Grammar::Empty => {}
+                item => flat.push(item),
+            }
+        }
+        match flat.len() {
+            0 => Grammar::Empty,
+            1 => flat.pop().unwrap(),
+            _ => Grammar::Seq(flat),
+        }
+    }
+
+    pub fn alt(items: impl IntoIterator<Item = Grammar>) -> Grammar {
+        let mut flat = Vec::new();
+        for item in items {
+            match item {
+                Grammar::Alt(items) => flat.extend(items),
+                item => flat.push(item),
+            }
+        }
+        match flat.len() {
+            // A choice between nothing never matches
+            0 => Grammar::Fail,
+            1 => flat.pop().unwrap(),
+            _ => Grammar::Alt(flat),
+        }
+    }
+
+    pub fn optional(item: Grammar) -> Grammar {
+        match item {
+            Grammar::Optional(_) | Grammar::Empty => item,
+            item => Grammar::Optional(Box::new(item)),
+        }
+    }
+
+    pub fn repeat(item: Grammar, sep: Option<Grammar>) -> Grammar {
+        Grammar::Repeat {
+            item: Box::new(item),
+            sep: sep.map(Box::new),
+        }
+    }
+
+    pub fn many(item: Grammar) -> Grammar {
+        Grammar::optional(Grammar::repeat(item, None))
+    }
+
+    // Named rules, outermost first, each listed once.
+    pub fn rules(&self) -> Vec<(&str, &Grammar)> {
+        let mut rules = Vec::new();
+        self.collect_rules(&mut rules);
+        rules
+    }
+
+    fn collect_rules<'g>(&'g self, rules: &mut Vec<(&'g str, &'g Grammar)>) {
+        match self {
+            Grammar::Rule(name, body) => {
+                if rules.iter().all(|(seen, _)| seen != name) {
+                    rules.push((name, body));
+                    body.collect_rules(rules);
+                }
+            }
+            Grammar::Predicate(item) | Grammar::Optional(item) => item.collect_rules(rules),
+            Grammar::Seq(items) | Grammar::Alt(items) | Grammar::Permutation(items) => {
+                items.iter().for_each(|item| item.collect_rules(rules))
+            }
+            Grammar::Repeat { item, sep } => {
+                item.collect_rules(rules);
+                if let Some(sep) = sep {
+                    sep.collect_rules(rules);
+                }
+            }
+            Grammar::Any
+            | Grammar::Literal(_)
+            | Grammar::Special(_)
+            | Grammar::Empty
+            | Grammar::Fail
+            | Grammar::Opaque => {}
+        }
+    }
+
+    // Whether any part of the grammar is a hand-written parser that couldn't describe itself.
+    pub fn has_opaque(&self) -> bool {
+        match self {
+            Grammar::Opaque => true,
+            Grammar::Rule(_, item) | Grammar::Predicate(item) | Grammar::Optional(item) => {
+                item.has_opaque()
+            }
+            Grammar::Seq(items) | Grammar::Alt(items) | Grammar::Permutation(items) => {
+                items.iter().any(Grammar::has_opaque)
+            }
+            Grammar::Repeat { item, sep } => {
+                item.has_opaque() || sep.as_deref().is_some_and(Grammar::has_opaque)
+            }
+            Grammar::Any
+            | Grammar::Literal(_)
+            | Grammar::Special(_)
+            | Grammar::Empty
+            | Grammar::Fail => false,
+        }
+    }
+
+    // The grammar as ISO-style EBNF, with the whole thing as rule `start` followed by every named
+    // rule it uses.
+    pub fn to_ebnf(&self, start: &str) -> String {
+        let mut out = String::new();
+        let mut rules = vec![(start, self)];
+        for (name, body) in self.rules() {
+            if name != start {
+                rules.push((name, body));
+            }
+        }
+        let width = rules.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (name, body) in rules {
+            let body: &Grammar = match body {
+                Grammar::Rule(_, body) if name == start => body,
+                body => body,
+            };
+            let _ = write!(out, "{name:width$} = ");
+            write_ebnf(body, Prec::Alt, &mut out);
+            out.push_str(" ;\n");
+        }
+        out
+    }
+
+    // The grammar as a standalone SVG document with one railroad diagram per rule, in the same
+    // order as `to_ebnf`.
+    pub fn to_railroad_svg(&self, start: &str) -> String {
+        let mut rules = vec![(start.to_string(), self)];
+        for (name, body) in self.rules() {
+            if name != start {
+                rules.push((name.to_string(), body));
+            }
+        }
+        railroad::render(&rules)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Prec {
+    Alt,
+    Seq,
+    Atom,
+}
+
+fn write_ebnf(grammar: &Grammar, prec: Prec, out: &mut String) {
+    let (own, text) = ebnf_term(grammar);
+    if own < prec {
+        let _ = write!(out, "( {text} )");
+    } else {
+        out.push_str(&text);
+    }
+}
+
+fn ebnf(grammar: &Grammar, prec: Prec) -> String {
+    let mut out = String::new();
+    write_ebnf(grammar, prec, &mut out);
+    out
+}
+
+fn join(items: &[Grammar], prec: Prec, sep: &str) -> String {
+    items
+        .iter()
+        .map(|item| ebnf(item, prec))
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+fn ebnf_term(grammar: &Grammar) -> (Prec, String) {
+    match grammar {
+        Grammar::Any => (Prec::Atom, "? any token ?".to_string()),
+        Grammar::Literal(text) => (Prec::Atom, format!("{text:?}")),
+        Grammar::Predicate(item) => match &**item {
+            Grammar::Any => (Prec::Atom, "? token satisfying a predicate ?".to_string()),
+            item => (
+                Prec::Atom,
+                format!("{} (* satisfying a predicate *)", ebnf(item, Prec::Atom)),
+            ),
+        },
+        Grammar::Special(text) => (Prec::Atom, format!("? {text} ?")),
+        Grammar::Rule(name, _) => (Prec::Atom, name.clone()),
+        Grammar::Seq(items) => (Prec::Seq, join(items, Prec::Seq, ", ")),
+        Grammar::Alt(items) if items.is_empty() => ebnf_term(&Grammar::Fail),
+        Grammar::Alt(items) => (Prec::Alt, join(items, Prec::Seq, " | ")),
+        Grammar::Permutation(items) => (
+            Prec::Atom,
+            format!(
+                "( {} ) (* each exactly once, in any order *)",
+                join(items, Prec::Seq, " | ")
+            ),
+        ),
+        Grammar::Optional(item) => match &**item {
+            Grammar::Repeat { item, sep: None } => {
+                (Prec::Atom, format!("{{ {} }}", ebnf(item, Prec::Alt)))
+            }
+            item => (Prec::Atom, format!("[ {} ]", ebnf(item, Prec::Alt))),
+        },
+        Grammar::Repeat { item, sep } => {
+            let item = ebnf(item, Prec::Seq);
+            match sep {
+                None => (Prec::Seq, format!("{item}, {{ {item} }}")),
+                Some(sep) => {
+                    let sep = ebnf(sep, Prec::Seq);
+                    (Prec::Seq, format!("{item}, {{ {sep}, {item} }}"))
+                }
+            }
+        }
+        Grammar::Empty => (Prec::Atom, "()".to_string()),
+        Grammar::Fail => (Prec::Atom, "? nothing ?".to_string()),
+        Grammar::Opaque => (Prec::Atom, "? hand-written parser ?".to_string()),
+    }
+}
+
+mod railroad {
+    // A small railroad diagram layout in the style of the usual JSON/SQL syntax diagrams. Each
+    // node is measured as a width plus its extent above and below the line running through it,
+    // then drawn left to right at a given position of that line.
+
+    use std::fmt::Write;
+
+    use super::{Grammar, ebnf_term};
+
+    const ARC: f64 = 10.0;
+    const GAP: f64 = 10.0;
+    const VS: f64 = 8.0;
+    const CHAR_WIDTH: f64 = 8.0;
+    const BOX_HEIGHT: f64 = 22.0;
+    const PAD: f64 = 20.0;
+    const TITLE_HEIGHT: f64 = 24.0;
+
+    enum Node {
+        Terminal(String),
+        NonTerminal(String),
+        Special(String),
+        Seq(Vec<Node>),
+        // The first option runs along the main line, the rest branch off below it
+        Choice(Vec<Node>),
+        OneOrMore(Box<Node>, Box<Node>),
+        Skip,
+    }
+
+    fn node(grammar: &Grammar) -> Node {
+        match grammar {
+            Grammar::Literal(text) => Node::Terminal(format!("{text:?}")),
+            Grammar::Rule(name, _) => Node::NonTerminal(name.clone()),
+            Grammar::Seq(items) => Node::Seq(items.iter().map(node).collect()),
+            Grammar::Alt(items) if !items.is_empty() => {
+                Node::Choice(items.iter().map(node).collect())
+            }
+            Grammar::Permutation(items) => Node::Seq(vec![
+                Node::Special("each once, any order".to_string()),
+                Node::OneOrMore(
+                    Box::new(Node::Choice(items.iter().map(node).collect())),
+                    Box::new(Node::Skip),
+                ),
+            ]),
+            Grammar::Optional(item) => Node::Choice(vec![Node::Skip, node(item)]),
+            Grammar::Repeat { item, sep } => Node::OneOrMore(
+                Box::new(node(item)),
+                Box::new(sep.as_deref().map_or(Node::Skip, node)),
+            ),
+            Grammar::Empty => Node::Skip,
+            Grammar::Predicate(item) => Node::Special(match &**item {
+                Grammar::Any => "token satisfying a predicate".to_string(),
+                item => format!("{} satisfying a predicate", ebnf_term(item).1),
+            }),
+            other => Node::Special(ebnf_term(other).1.trim_matches([' ', '?']).to_string()),
+        }
+    }
+
+    struct Size {
+        width: f64,
+        up: f64,
+        down: f64,
+    }
+
+    impl Node {
+        fn size(&self) -> Size {
+            match self {
+                Node::Terminal(text) | Node::NonTerminal(text) | Node::Special(text) => Size {
+                    width: text.chars().count() as f64 * CHAR_WIDTH + 2.0 * GAP,
+                    up: BOX_HEIGHT / 2.0,
+                    down: BOX_HEIGHT / 2.0,
+                },
+                Node::Seq(items) => items.iter().map(Node::size).fold(
+                    Size {
+                        width: GAP * (items.len().saturating_sub(1)) as f64,
+                        up: 0.0,
+                        down: 0.0,
+                    },
+                    |acc, size| Size {
+                        width: acc.width + size.width,
+                        up: acc.up.max(size.up),
+                        down: acc.down.max(size.down),
+                    },
+                ),
+                Node::Choice(items) => {
+                    let sizes: Vec<_> = items.iter().map(Node::size).collect();
+                    Size {
+                        width: sizes.iter().map(|s| s.width).fold(0.0, f64::max) + 4.0 * ARC,
+                        up: sizes[0].up,
+                        down: sizes[0].down
+                            + sizes[1..]
+                                .iter()
+                                .map(|s| (VS + s.up).max(2.0 * ARC) + s.down)
+                                .sum::<f64>(),
+                    }
+                }
+                Node::OneOrMore(item, sep) => {
+                    let (item, sep) = (item.size(), sep.size());
+                    Size {
+                        width: item.width.max(sep.width) + 2.0 * ARC,
+                        up: item.up,
+                        down: item.down + (VS + sep.up).max(2.0 * ARC) + sep.down,
+                    }
+                }
+                Node::Skip => Size {
+                    width: 0.0,
+                    up: 0.0,
+                    down: 0.0,
+                },
+            }
+        }
+
+        // Draws the node with its line entering at (x, y), returning the SVG elements.
+        fn draw(&self, x: f64, y: f64, out: &mut String) {
+            let size = self.size();
+            match self {
+                Node::Terminal(text) | Node::NonTerminal(text) | Node::Special(text) => {
+                    let (class, rx) = match self {
+                        Node::Terminal(_) => ("terminal", BOX_HEIGHT / 2.0),
+                        Node::NonTerminal(_) => ("nonterminal", 0.0),
+                        _ => ("special", 0.0),
+                    };
+                    let _ = writeln!(
+                        out,
+                        r#"<g class="{class}"><rect x="{x}" y="{}" width="{}" height="{BOX_HEIGHT}" rx="{rx}"/><text x="{}" y="{}">{}</text></g>"#,
+                        y - BOX_HEIGHT / 2.0,
+                        size.width,
+                        x + size.width / 2.0,
+                        y + 4.0,
+                        escape(text),
+                    );
+                }
+                Node::Seq(items) => {
+                    let mut x = x;
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            line(out, x, y, x + GAP, y);
+                            x += GAP;
+                        }
+                        item.draw(x, y, out);
+                        x += item.size().width;
+                    }
+                }
+                Node::Choice(items) => {
+                    let inner = size.width - 4.0 * ARC;
+                    let right = x + size.width;
+                    let mut branch_y = y;
+                    let mut below = 0.0;
+                    for (i, item) in items.iter().enumerate() {
+                        let item_size = item.size();
+                        if i == 0 {
+                            below = item_size.down;
+                        } else {
+                            branch_y += below + (VS + item_size.up).max(2.0 * ARC);
+                            below = item_size.down;
+                            let _ = writeln!(
+                                out,
+                                r#"<path d="M{x} {y} q{ARC} 0 {ARC} {ARC} V{} q0 {ARC} {ARC} {ARC}"/>"#,
+                                branch_y - ARC,
+                            );
+                            let _ = writeln!(
+                                out,
+                                r#"<path d="M{} {branch_y} q{ARC} 0 {ARC} -{ARC} V{} q0 -{ARC} {ARC} -{ARC}"/>"#,
+                                right - 2.0 * ARC,
+                                y + ARC,
+                            );
+                        }
+                        let item_x = x + 2.0 * ARC + (inner - item_size.width) / 2.0;
+                        line(out, x + if i == 0 { 0.0 } else { 2.0 * ARC }, branch_y, item_x, branch_y);
+                        item.draw(item_x, branch_y, out);
+                        let item_end = item_x + item_size.width;
+                        line(out, item_end, branch_y, right - if i == 0 { 0.0 } else { 2.0 * ARC }, branch_y);
+                    }
+                }
+                Node::OneOrMore(item, sep) => {
+                    let (item_size, sep_size) = (item.size(), sep.size());
+                    let inner = size.width - 2.0 * ARC;
+                    let right = x + size.width;
+                    let item_x = x + ARC + (inner - item_size.width) / 2.0;
+                    line(out, x, y, item_x, y);
+                    item.draw(item_x, y, out);
+                    line(out, item_x + item_size.width, y, right, y);
+
+                    let loop_y = y + item_size.down + (VS + sep_size.up).max(2.0 * ARC);
+                    let _ = writeln!(
+                        out,
+                        r#"<path d="M{} {y} q{ARC} 0 {ARC} {ARC} V{} q0 {ARC} -{ARC} {ARC}"/>"#,
+                        right - ARC,
+                        loop_y - ARC,
+                    );
+                    let _ = writeln!(
+                        out,
+                        r#"<path d="M{} {loop_y} q-{ARC} 0 -{ARC} -{ARC} V{} q0 -{ARC} {ARC} -{ARC}"/>"#,
+                        x + ARC,
+                        y + ARC,
+                    );
+                    let sep_x = x + ARC + (inner - sep_size.width) / 2.0;
+                    line(out, x + ARC, loop_y, sep_x, loop_y);
+                    sep.draw(sep_x, loop_y, out);
+                    line(out, sep_x + sep_size.width, loop_y, right - ARC, loop_y);
+                }
+                Node::Skip => {}
+            }
+        }
+    }
+
+    fn line(out: &mut String, x1: f64, y1: f64, x2: f64, y2: f64) {
+        if x1 != x2 || y1 != y2 {
+            let _ = writeln!(out, r#"<path d="M{x1} {y1} L{x2} {y2}"/>"#);
+        }
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    pub(super) fn render(rules: &[(String, &Grammar)]) -> String {
+        let mut body = String::new();
+        let mut y = PAD;
+        let mut width: f64 = 0.0;
+        for (name, grammar) in rules {
+            let grammar: &Grammar = match grammar {
+                Grammar::Rule(rule, body) if rule == name => body,
+                grammar => grammar,
+            };
+            let node = node(grammar);
+            let size = node.size();
+            let _ = writeln!(
+                body,
+                r#"<text class="title" x="{PAD}" y="{}">{}</text>"#,
+                y + TITLE_HEIGHT / 2.0,
+                escape(name),
+            );
+            let line_y = y + TITLE_HEIGHT + size.up;
+            let start = PAD;
+            let _ = writeln!(
+                body,
+                r#"<path d="M{start} {} v20 M{} {} v20"/>"#,
+                line_y - 10.0,
+                start + 4.0,
+                line_y - 10.0,
+            );
+            line(&mut body, start + 4.0, line_y, start + 4.0 + GAP, line_y);
+            node.draw(start + 4.0 + GAP, line_y, &mut body);
+            let end = start + 4.0 + GAP + size.width;
+            line(&mut body, end, line_y, end + GAP, line_y);
+            let _ = writeln!(
+                body,
+                r#"<path d="M{} {} v20 M{} {} v20"/>"#,
+                end + GAP,
+                line_y - 10.0,
+                end + GAP + 4.0,
+                line_y - 10.0,
+            );
+            width = width.max(end + GAP + 4.0 + PAD);
+            y = line_y + size.down + 2.0 * PAD;
+        }
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{y}" viewBox="0 0 {width} {y}">
+<style>
+path {{ stroke: black; stroke-width: 2; fill: none; }}
+rect {{ stroke: black; stroke-width: 2; fill: #ffffdd; }}
+.nonterminal rect {{ fill: #ddeeff; }}
+.special rect {{ fill: #eeeeee; stroke-dasharray: 4 2; }}
+text {{ font: 14px monospace; text-anchor: middle; }}
+.special text {{ font-style: italic; }}
+.title {{ font-weight: bold; text-anchor: start; }}
+</style>
+{body}</svg>
+"#
+        )
+    }
+}
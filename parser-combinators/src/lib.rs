@@ -1,5 +1,66 @@
+use std::fmt;
 use std::marker::PhantomData;
 
+use r#gen::{attempt, generate_many, generate_sep, SATISFY_ATTEMPTS};
+
+pub mod bench;
+pub mod check;
+pub mod fuzz;
+mod from_parse;
+mod r#gen;
+mod grammar;
+mod pattern;
+mod stream;
+mod syntax;
+mod text;
+
+pub use from_parse::*;
+pub use r#gen::*;
+pub use grammar::*;
+pub use pattern::*;
+pub use stream::*;
+pub use syntax::*;
+pub use text::*;
+
+/// `#[derive(Parse)]`, see `parser-combinators-derive`. Mistakes in its attributes are compile
+/// errors. Unknown keys are refused:
+///
+/// ```compile_fail
+/// #[derive(Clone, parser_combinators::Parse)]
+/// struct Pair(#[parse(suffix = ",")] u8, u8);
+/// ```
+///
+/// as are keys in the wrong place, like `sep` on a variant:
+///
+/// ```compile_fail
+/// #[derive(Clone, parser_combinators::Parse)]
+/// enum Dir {
+///     #[parse(sep = ",")]
+///     Up,
+/// }
+/// ```
+///
+/// and values that aren't string literals:
+///
+/// ```compile_fail
+/// #[derive(Clone, parser_combinators::Parse)]
+/// struct Pair(#[parse(sep = ',')] u8, u8);
+/// ```
+///
+/// The same types with correct attributes compile:
+///
+/// ```
+/// #[derive(Clone, parser_combinators::Parse)]
+/// struct Pair(#[parse(sep = ",")] u8, u8);
+///
+/// #[derive(Clone, parser_combinators::Parse)]
+/// enum Dir {
+///     #[parse(prefix = "^")]
+///     Up,
+/// }
+/// ```
+pub use parser_combinators_derive::Parse;
+
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum ErrorType {
@@ -7,6 +68,23 @@ pub enum ErrorType {
     Read,
     Satisfy,
     Choice,
+    Indent { expected: usize, found: usize },
+    Section(usize, Box<ErrorType>),
+}
+
+impl fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorType::Msg(msg) => write!(f, "{msg}"),
+            ErrorType::Read => write!(f, "unexpected end of input"),
+            ErrorType::Satisfy => write!(f, "unexpected token"),
+            ErrorType::Choice => write!(f, "no alternative matched"),
+            ErrorType::Indent { expected, found } => {
+                write!(f, "expected indentation {expected}, found {found}")
+            }
+            ErrorType::Section(n, typ) => write!(f, "in section {n}: {typ}"),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -33,6 +111,21 @@ pub trait Parser<'a, T: 'a>: Sized {
 
     fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O>;
 
+    // Describes the language this parser accepts. Combinators build this from their parts;
+    // hand-written parsers are opaque unless wrapped with `named`.
+    fn grammar(&self) -> Grammar {
+        Grammar::Opaque
+    }
+
+    // Appends a random input that this parser accepts to `out`, returning the value it should
+    // parse to, or `None` if that isn't possible (e.g. for hand-written parsers).
+    fn generate(&self, _: &mut Gen<'a, T>, _: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        None
+    }
+
     fn disj<P>(self, other: P) -> Disj<Self, P>
     where
         P: Parser<'a, T, O = Self::O>,
@@ -43,6 +136,13 @@ pub trait Parser<'a, T: 'a>: Sized {
         }
     }
 
+    fn named(self, name: &str) -> Named<Self> {
+        Named {
+            p: self,
+            name: name.to_string(),
+        }
+    }
+
     fn backtrack(self) -> Backtrack<Self> {
         Backtrack(self)
     }
@@ -51,32 +151,71 @@ pub trait Parser<'a, T: 'a>: Sized {
     where
         F: Fn(&Self::O) -> bool,
     {
-        Satisfy { p: self, f: f }
+        Satisfy { p: self, f }
     }
 
     fn map<F, O2>(self, f: F) -> Map<Self, F>
     where
         F: Fn(Self::O) -> O2,
     {
-        Map { p: self, f: f }
+        Map { p: self, f }
+    }
+
+    // Like `map`, but `f` can reject the result by returning `None`, failing like `satisfy`.
+    fn try_map<F, O2>(self, f: F) -> TryMap<Self, F>
+    where
+        F: Fn(Self::O) -> Option<O2>,
+    {
+        TryMap { p: self, f }
     }
 
     fn map_const<O2: Clone>(self, x: O2) -> MapConst<Self, O2> {
-        MapConst { p: self, x: x }
+        MapConst { p: self, x }
+    }
+
+    // Like `map`, but with a (partial) inverse `g` so that the result is `Syntax` if `self` is.
+    fn iso<F, G, O2>(self, f: F, g: G) -> Iso<Self, F, G>
+    where
+        F: Fn(Self::O) -> O2,
+        G: Fn(&O2) -> Option<Self::O>,
+    {
+        syntax::iso(self, f, g)
+    }
+
+    // Like `map`, but `g` prints results straight to tokens that `self` would parse back.
+    fn map_print<F, G, O2>(self, f: F, g: G) -> MapPrint<Self, F, G>
+    where
+        F: Fn(Self::O) -> O2,
+        G: Fn(&O2) -> Option<Vec<T>>,
+    {
+        syntax::map_print(self, f, g)
+    }
+
+    // Keeps the result as it is, printing it with `g` as in `map_print`.
+    fn print_with<G>(self, g: G) -> PrintWith<Self, Self::O, G>
+    where
+        G: Fn(&Self::O) -> Option<Vec<T>>,
+    {
+        syntax::map_print(self, std::convert::identity, g)
+    }
+
+    // Discards the result, always printing `tokens`. Useful for separators and whitespace.
+    fn print_as(self, tokens: impl IntoIterator<Item = T>) -> PrintAs<Self, T> {
+        syntax::print_as(self, tokens)
     }
 
     fn seql<O2, P2>(self, p2: P2) -> Seql<Self, P2>
     where
         P2: Parser<'a, T, O = O2>,
     {
-        Seql { p1: self, p2: p2 }
+        Seql { p1: self, p2 }
     }
 
     fn seqr<O2, P2>(self, p2: P2) -> Seqr<Self, P2>
     where
         P2: Parser<'a, T, O = O2>,
     {
-        Seqr { p1: self, p2: p2 }
+        Seqr { p1: self, p2 }
     }
 
     fn optional(self) -> Optional<Self> {
@@ -95,42 +234,42 @@ pub trait Parser<'a, T: 'a>: Sized {
     where
         PS: Parser<'a, T, O = S>,
     {
-        SepBy { p: self, sep: sep }
+        SepBy { p: self, sep }
     }
 
     fn sep_by1<S, PS>(self, sep: PS) -> SepBy1<Self, PS>
     where
         PS: Parser<'a, T, O = S>,
     {
-        SepBy1 { p: self, sep: sep }
+        SepBy1 { p: self, sep }
     }
 
     fn end_by<S, PS>(self, sep: PS) -> EndBy<Self, PS>
     where
         PS: Parser<'a, T, O = S>,
     {
-        EndBy { p: self, sep: sep }
+        EndBy { p: self, sep }
     }
 
     fn end_by1<S, PS>(self, sep: PS) -> EndBy1<Self, PS>
     where
         PS: Parser<'a, T, O = S>,
     {
-        EndBy1 { p: self, sep: sep }
+        EndBy1 { p: self, sep }
     }
 
     fn sep_end_by<S, PS>(self, sep: PS) -> SepEndBy<Self, PS>
     where
         PS: Parser<'a, T, O = S>,
     {
-        SepEndBy { p: self, sep: sep }
+        SepEndBy { p: self, sep }
     }
 
     fn sep_end_by1<S, PS>(self, sep: PS) -> SepEndBy1<Self, PS>
     where
         PS: Parser<'a, T, O = S>,
     {
-        SepEndBy1 { p: self, sep: sep }
+        SepEndBy1 { p: self, sep }
     }
 }
 
@@ -151,10 +290,49 @@ where
             if tokens.len() - e1.rest.len() > 1 {
                 Err(e1)
             } else {
-                self.p2.parse(tokens).or_else(|e2| Err(e1.combine(e2)))
+                self.p2.parse(tokens).map_err(|e2| e1.combine(e2))
             }
         })
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::alt([self.p1.grammar(), self.p2.grammar()])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        if g.rng.coin() {
+            attempt(out, |out| self.p1.generate(g, out)).or_else(|| self.p2.generate(g, out))
+        } else {
+            attempt(out, |out| self.p2.generate(g, out)).or_else(|| self.p1.generate(g, out))
+        }
+    }
+}
+
+pub struct Named<P> {
+    p: P,
+    name: String,
+}
+
+impl<'a, T: 'a, P: Parser<'a, T>> Parser<'a, T> for Named<P> {
+    type O = P::O;
+
+    fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
+        self.p.parse(tokens)
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Rule(self.name.clone(), Box::new(self.p.grammar()))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out)
+    }
 }
 
 pub struct Backtrack<P>(P);
@@ -165,13 +343,22 @@ where
 {
     type O = O;
     fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, O> {
-        self.0.parse(tokens).or_else(|ParseError { typ, rest: _ }| {
-            Err(ParseError {
-                typ: typ,
-                rest: tokens,
-            })
+        self.0.parse(tokens).map_err(|ParseError { typ, rest: _ }| ParseError {
+            typ,
+            rest: tokens,
         })
     }
+
+    fn grammar(&self) -> Grammar {
+        self.0.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.0.generate(g, out)
+    }
 }
 
 pub struct Satisfy<P, F> {
@@ -192,10 +379,23 @@ where
         } else {
             Err(ParseError {
                 typ: ErrorType::Satisfy,
-                rest: rest,
+                rest,
             })
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Predicate(Box::new(self.p.grammar()))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        (0..SATISFY_ATTEMPTS).find_map(|_| {
+            attempt(out, |out| self.p.generate(g, out).filter(|res| (self.f)(res)))
+        })
+    }
 }
 
 pub struct Map<P, F> {
@@ -213,6 +413,52 @@ where
         let (res, rest) = self.p.parse(tokens)?;
         Ok(((self.f)(res), rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        self.p.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out).map(&self.f)
+    }
+}
+
+pub struct TryMap<P, F> {
+    p: P,
+    f: F,
+}
+
+impl<'a, T: 'a, O, O2, P, F> Parser<'a, T> for TryMap<P, F>
+where
+    P: Parser<'a, T, O = O>,
+    F: Fn(O) -> Option<O2>,
+{
+    type O = O2;
+    fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, O2> {
+        let (res, rest) = self.p.parse(tokens)?;
+        match (self.f)(res) {
+            Some(res) => Ok((res, rest)),
+            None => Err(ParseError {
+                typ: ErrorType::Satisfy,
+                rest,
+            }),
+        }
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Predicate(Box::new(self.p.grammar()))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        (0..SATISFY_ATTEMPTS)
+            .find_map(|_| attempt(out, |out| self.p.generate(g, out).and_then(&self.f)))
+    }
 }
 
 pub struct MapConst<P, O> {
@@ -230,6 +476,17 @@ where
         let (_, rest) = self.p.parse(tokens)?;
         Ok((self.x.clone(), rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        self.p.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out).map(|_| self.x.clone())
+    }
 }
 
 impl<'a, T: 'a, O1, O2, P1, P2> Parser<'a, T> for (P1, P2)
@@ -245,6 +502,17 @@ where
         let (res2, rest2) = p2.parse(rest1)?;
         Ok(((res1, res2), rest2))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::seq([self.0.grammar(), self.1.grammar()])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        Some((self.0.generate(g, out)?, self.1.generate(g, out)?))
+    }
 }
 
 pub struct Seql<P1, P2> {
@@ -264,6 +532,19 @@ where
         let (_, rest2) = self.p2.parse(rest1)?;
         Ok((res, rest2))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::seq([self.p1.grammar(), self.p2.grammar()])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let res = self.p1.generate(g, out)?;
+        self.p2.generate(g, out)?;
+        Some(res)
+    }
 }
 
 pub struct Seqr<P1, P2> {
@@ -283,6 +564,18 @@ where
         let (res, rest2) = self.p2.parse(rest1)?;
         Ok((res, rest2))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::seq([self.p1.grammar(), self.p2.grammar()])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p1.generate(g, out)?;
+        self.p2.generate(g, out)
+    }
 }
 
 pub struct Optional<P>(P);
@@ -299,6 +592,21 @@ where
             Err(_) => Ok((None, tokens)),
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::optional(self.0.grammar())
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        if g.rng.coin() {
+            attempt(out, |out| self.0.generate(g, out)).map(Some).or(Some(None))
+        } else {
+            Some(None)
+        }
+    }
 }
 
 pub struct Many<P>(P);
@@ -318,6 +626,18 @@ where
         }
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::many(self.0.grammar())
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(0);
+        generate_many(&self.0, n, g, out)
+    }
 }
 
 pub struct Many1<P>(P);
@@ -337,6 +657,18 @@ where
         }
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::repeat(self.0.grammar(), None)
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(1);
+        generate_many(&self.0, n, g, out)
+    }
 }
 
 pub struct SepBy<P, PS> {
@@ -366,6 +698,18 @@ where
             }
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::optional(Grammar::repeat(self.p.grammar(), Some(self.sep.grammar())))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(0);
+        generate_sep(&self.p, &self.sep, n, false, g, out)
+    }
 }
 
 pub struct SepBy1<P, PS> {
@@ -391,6 +735,18 @@ where
         }
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::repeat(self.p.grammar(), Some(self.sep.grammar()))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(1);
+        generate_sep(&self.p, &self.sep, n, false, g, out)
+    }
 }
 
 pub struct EndBy<P, PS> {
@@ -416,6 +772,18 @@ where
         }
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::many(Grammar::seq([self.p.grammar(), self.sep.grammar()]))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(0);
+        generate_sep(&self.p, &self.sep, n, true, g, out)
+    }
 }
 
 pub struct EndBy1<P, PS> {
@@ -442,6 +810,18 @@ where
         }
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::repeat(Grammar::seq([self.p.grammar(), self.sep.grammar()]), None)
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let n = g.count(1);
+        generate_sep(&self.p, &self.sep, n, true, g, out)
+    }
 }
 
 pub struct SepEndBy<P, PS> {
@@ -469,12 +849,27 @@ where
                 }
                 let rest = match self.sep.parse(rest) {
                     Ok((_, rest)) => rest,
-                    Err(ParseError { typ: _, rest }) => rest,
+                    Err(_) => rest,
                 };
                 Ok((res, rest))
             }
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::optional(Grammar::seq([
+            Grammar::repeat(self.p.grammar(), Some(self.sep.grammar())),
+            Grammar::optional(self.sep.grammar()),
+        ]))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let (n, trailing) = (g.count(0), g.rng.coin());
+        generate_sep(&self.p, &self.sep, n, trailing, g, out)
+    }
 }
 
 pub struct SepEndBy1<P, PS> {
@@ -500,10 +895,87 @@ where
         }
         let rest = match self.sep.parse(rest) {
             Ok((_, rest)) => rest,
-            Err(ParseError { typ: _, rest }) => rest,
+            Err(_) => rest,
         };
         Ok((res, rest))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::seq([
+            Grammar::repeat(self.p.grammar(), Some(self.sep.grammar())),
+            Grammar::optional(self.sep.grammar()),
+        ])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let (n, trailing) = (g.count(1), g.rng.coin());
+        generate_sep(&self.p, &self.sep, n, trailing, g, out)
+    }
+}
+
+pub struct ParseIter<'a, T, P, PS> {
+    p: P,
+    sep: PS,
+    rest: &'a [T],
+    done: bool,
+}
+
+impl<'a, T: 'a, O, P, PS, S> Iterator for ParseIter<'a, T, P, PS>
+where
+    P: Parser<'a, T, O = O>,
+    PS: Parser<'a, T, O = S>,
+{
+    type Item = Result<O, ParseError<'a, T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.rest.is_empty() {
+            return None;
+        }
+        let res = self.p.parse(self.rest).and_then(|(one, rest)| {
+            if rest.is_empty() {
+                return Ok((one, rest));
+            }
+            let (_, rest) = self.sep.parse(rest)?;
+            Ok((one, rest))
+        });
+        // Neither the record nor the separator consumed anything, so the next call would
+        // parse the same input again, forever.
+        let res = res.and_then(|(one, rest)| {
+            if rest.len() == self.rest.len() {
+                return Err(ParseError {
+                    typ: ErrorType::Msg("record and separator consumed nothing".to_string()),
+                    rest,
+                });
+            }
+            Ok((one, rest))
+        });
+        match res {
+            Ok((one, rest)) => {
+                self.rest = rest;
+                Some(Ok(one))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// Lazily parses `input` as `p`s separated (and optionally terminated) by `sep`, like
+// `sep_end_by`, yielding one record at a time. The first error ends the iteration, so all of the
+// input has been consumed iff every item was `Ok`. A record that, with its separator, consumes
+// nothing is an error rather than an endless stream of records.
+pub fn parse_iter<'a, T, P, PS>(p: P, sep: PS, input: &'a [T]) -> ParseIter<'a, T, P, PS> {
+    ParseIter {
+        p,
+        sep,
+        rest: input,
+        done: false,
+    }
 }
 
 impl<'a, F, T: 'a, O> Parser<'a, T> for F
@@ -528,6 +1000,17 @@ where
     fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
         Ok(((self.0)(), tokens))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Empty
+    }
+
+    fn generate(&self, _: &mut Gen<'a, T>, _: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        Some((self.0)())
+    }
 }
 
 pub fn pure_with<F, O>(f: F) -> PureWith<F>
@@ -545,6 +1028,17 @@ impl<'a, T: 'a, O: Clone> Parser<'a, T> for Pure<O> {
     fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
         Ok((self.0.clone(), tokens))
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Empty
+    }
+
+    fn generate(&self, _: &mut Gen<'a, T>, _: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        Some(self.0.clone())
+    }
 }
 
 pub fn pure<O>(x: O) -> Pure<O> {
@@ -562,6 +1056,17 @@ impl<'a, T: 'a, O> Parser<'a, T> for Fail<O> {
             rest: tokens,
         })
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Fail
+    }
+
+    fn generate(&self, _: &mut Gen<'a, T>, _: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        None
+    }
 }
 
 pub fn fail<O>(typ: ErrorType) -> Fail<O> {
@@ -579,6 +1084,17 @@ impl<'a, T: 'a, O> Parser<'a, T> for FailWithMessage<O> {
             rest: tokens,
         })
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Fail
+    }
+
+    fn generate(&self, _: &mut Gen<'a, T>, _: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        None
+    }
 }
 
 pub fn fail_with_message<O>(msg: String) -> FailWithMessage<O> {
@@ -599,6 +1115,19 @@ impl<'a, T: 'a> Parser<'a, T> for Read {
             }),
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Any
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let tok = g.alphabet.get(g.rng.below(g.alphabet.len()))?;
+        out.push(tok.clone());
+        Some(tok)
+    }
 }
 
 pub fn read() -> Read {
@@ -624,6 +1153,21 @@ where
             rest: tokens,
         })
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::alt(self.0.iter().map(|p| p.grammar()))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        let start = g.rng.below(self.0.len());
+        (0..self.0.len()).find_map(|i| {
+            let p = &self.0[(start + i) % self.0.len()];
+            attempt(out, |out| p.generate(g, out))
+        })
+    }
 }
 
 pub fn choice<'a, T: 'a, P, O>(ps: &'a [P]) -> Choice<'a, P>
@@ -632,3 +1176,122 @@ where
 {
     Choice(ps)
 }
+
+pub struct Permutation<PS>(PS);
+
+// Accepts each parser exactly once, in any order, returning results in declaration order.
+// A member only counts as matched in the loop if it consumes input, so members that can succeed
+// without consuming anything (e.g. `p.optional()`) are optional: they are run once more at the
+// end of the loop if they never matched.
+pub fn permutation<PS>(ps: PS) -> Permutation<PS> {
+    Permutation(ps)
+}
+
+macro_rules! impl_permutation {
+    ($(($P:ident, $O:ident, $res:ident, $idx:tt)),+) => {
+        impl<'a, T: 'a, $($O, $P),+> Parser<'a, T> for Permutation<($($P,)+)>
+        where
+            $($P: Parser<'a, T, O = $O>,)+
+        {
+            type O = ($($O,)+);
+
+            fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
+                let mut rest = tokens;
+                $(let mut $res: Option<$O> = None;)+
+                'round: loop {
+                    $(
+                        if $res.is_none() {
+                            match self.0.$idx.parse(rest) {
+                                Ok((one, rest1)) if rest1.len() < rest.len() => {
+                                    $res = Some(one);
+                                    rest = rest1;
+                                    continue 'round;
+                                }
+                                Ok(_) => {}
+                                // Check to prevent backtracking by more than 1
+                                Err(e) if rest.len() - e.rest.len() > 1 => return Err(e),
+                                Err(_) => {}
+                            }
+                        }
+                    )+
+                    break;
+                }
+                $(
+                    let $res = match $res {
+                        Some(one) => one,
+                        None => {
+                            let (one, rest1) = self.0.$idx.parse(rest)?;
+                            rest = rest1;
+                            one
+                        }
+                    };
+                )+
+                Ok((($($res,)+), rest))
+            }
+
+            fn grammar(&self) -> Grammar {
+                Grammar::Permutation(vec![$(self.0.$idx.grammar()),+])
+            }
+
+            fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+            where
+                T: Clone,
+            {
+                // Generate each member separately, then lay them out in a random order
+                let mut parts = Vec::new();
+                $(
+                    let mut part = Vec::new();
+                    let $res = self.0.$idx.generate(g, &mut part)?;
+                    parts.push(part);
+                )+
+                g.rng.shuffle(&mut parts);
+                out.extend(parts.into_iter().flatten());
+                Some(($($res,)+))
+            }
+        }
+    };
+}
+
+impl_permutation!((P1, O1, res1, 0));
+impl_permutation!((P1, O1, res1, 0), (P2, O2, res2, 1));
+impl_permutation!((P1, O1, res1, 0), (P2, O2, res2, 1), (P3, O3, res3, 2));
+impl_permutation!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3)
+);
+impl_permutation!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3),
+    (P5, O5, res5, 4)
+);
+impl_permutation!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3),
+    (P5, O5, res5, 4),
+    (P6, O6, res6, 5)
+);
+impl_permutation!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3),
+    (P5, O5, res5, 4),
+    (P6, O6, res6, 5),
+    (P7, O7, res7, 6)
+);
+impl_permutation!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3),
+    (P5, O5, res5, 4),
+    (P6, O6, res6, 5),
+    (P7, O7, res7, 6),
+    (P8, O8, res8, 7)
+);
@@ -0,0 +1,534 @@
+// Parsers from a small regular-expression dialect, for one-off formats where chaining
+// `read().satisfy(...)` is overkill. Supported are literals, `.` (anything but a newline), classes
+// like `[a-z_]` and `[^,]`, the escapes `\d \w \s` (and their negations `\D \W \S`), `\n` and
+// `\t`, repetition with `* + ? {n} {n,} {n,m}` (lazy with a trailing `?`), alternation with `|`,
+// capturing `(...)` and non-capturing `(?:...)` groups, and the anchors `^` and `$`, which match
+// at the start and end of a line.
+//
+// A pattern always matches at the start of the input, like any other parser, and picks the match
+// a backtracking regex engine would. Backtracking remembers which (instruction, position) pairs
+// have already failed, so matching takes time linear in the input for a given pattern.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ErrorType, Grammar, ParseError, ParseResult, Parser};
+
+// Counted repetitions are expanded into copies of the repeated pattern, up to this many.
+const MAX_PROGRAM: usize = 1 << 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Debug, Clone)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl Class {
+    fn of(ranges: &[(char, char)], negated: bool) -> Self {
+        Class {
+            ranges: ranges.to_vec(),
+            negated,
+        }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != self.negated
+    }
+}
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+const SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+#[derive(Debug, Clone)]
+enum Node {
+    Class(Class),
+    Seq(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+    Group(Option<usize>, Box<Node>),
+    LineStart,
+    LineEnd,
+}
+
+struct PatternParser<'s> {
+    chars: &'s [char],
+    pos: usize,
+    groups: usize,
+}
+
+impl PatternParser<'_> {
+    fn error<X>(&self, message: &str) -> Result<X, PatternError> {
+        Err(PatternError {
+            position: self.pos,
+            message: message.to_string(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn alt(&mut self) -> Result<Node, PatternError> {
+        let mut alternatives = vec![self.seq()?];
+        while self.eat('|') {
+            alternatives.push(self.seq()?);
+        }
+        Ok(match alternatives.len() {
+            1 => alternatives.pop().expect("one alternative"),
+            _ => Node::Alt(alternatives),
+        })
+    }
+
+    fn seq(&mut self) -> Result<Node, PatternError> {
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            items.push(self.quantified(atom)?);
+        }
+        Ok(Node::Seq(items))
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let c = self.peek().expect("atom isn't at the end");
+        self.pos += 1;
+        Ok(match c {
+            '(' => {
+                let group = if self.eat('?') {
+                    if !self.eat(':') {
+                        return self.error("expected `:` after `(?`");
+                    }
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+                let node = self.alt()?;
+                if !self.eat(')') {
+                    return self.error("unclosed group");
+                }
+                Node::Group(group, Box::new(node))
+            }
+            '[' => Node::Class(self.class()?),
+            '.' => Node::Class(Class::of(&[('\n', '\n')], true)),
+            '^' => Node::LineStart,
+            '$' => Node::LineEnd,
+            '\\' => Node::Class(self.escape()?),
+            '*' | '+' | '?' | '{' => {
+                self.pos -= 1;
+                return self.error("nothing to repeat");
+            }
+            c => Node::Class(Class::of(&[(c, c)], false)),
+        })
+    }
+
+    // The class for the escape after a `\`.
+    fn escape(&mut self) -> Result<Class, PatternError> {
+        let Some(c) = self.peek() else {
+            return self.error("trailing `\\`");
+        };
+        self.pos += 1;
+        Ok(match c {
+            'd' => Class::of(DIGIT, false),
+            'D' => Class::of(DIGIT, true),
+            'w' => Class::of(WORD, false),
+            'W' => Class::of(WORD, true),
+            's' => Class::of(SPACE, false),
+            'S' => Class::of(SPACE, true),
+            'n' => Class::of(&[('\n', '\n')], false),
+            't' => Class::of(&[('\t', '\t')], false),
+            c if c.is_ascii_alphanumeric() => {
+                self.pos -= 1;
+                return self.error("unknown escape");
+            }
+            c => Class::of(&[(c, c)], false),
+        })
+    }
+
+    // The rest of a `[...]` class, after the `[`.
+    fn class(&mut self) -> Result<Class, PatternError> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(c) = self.peek() else {
+                return self.error("unclosed class");
+            };
+            self.pos += 1;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let lo = if c == '\\' {
+                let escaped = self.escape()?;
+                if escaped.negated {
+                    self.pos -= 2;
+                    return self.error("negated escapes aren't allowed in classes");
+                }
+                match escaped.ranges[..] {
+                    [(lo, hi)] if lo == hi => lo,
+                    _ => {
+                        ranges.extend(escaped.ranges);
+                        continue;
+                    }
+                }
+            } else {
+                c
+            };
+            let is_range = self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']');
+            if is_range && self.chars.get(self.pos + 1).is_some() {
+                self.pos += 1;
+                let mut hi = self.peek().expect("checked above");
+                self.pos += 1;
+                if hi == '\\' {
+                    let escaped = self.escape()?;
+                    match escaped.ranges[..] {
+                        [(c, d)] if c == d && !escaped.negated => hi = c,
+                        _ => return self.error("invalid range end"),
+                    }
+                }
+                if hi < lo {
+                    return self.error("range out of order");
+                }
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Ok(Class { ranges, negated })
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        String::from_iter(&self.chars[start..self.pos]).parse().ok()
+    }
+
+    fn quantified(&mut self, node: Node) -> Result<Node, PatternError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                let start = self.pos;
+                self.pos += 1;
+                let Some(min) = self.number() else {
+                    return self.error("expected a number in repetition");
+                };
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') {
+                        None
+                    } else {
+                        match self.number() {
+                            Some(max) => Some(max),
+                            None => return self.error("expected a number in repetition"),
+                        }
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some('}') {
+                    return self.error("unclosed repetition");
+                }
+                if max.is_some_and(|max| max < min) {
+                    self.pos = start;
+                    return self.error("repetition out of order");
+                }
+                (min, max)
+            }
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        if matches!(node, Node::LineStart | Node::LineEnd) {
+            return self.error("anchors can't be repeated");
+        }
+        let greedy = !self.eat('?');
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return self.error("nested repetition needs a group");
+        }
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+            greedy,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(Class),
+    // Try the first branch, then the second.
+    Split(usize, usize),
+    Jmp(usize),
+    Save(usize),
+    LineStart,
+    LineEnd,
+    Match,
+}
+
+fn compile(node: &Node, prog: &mut Vec<Inst>) -> Result<(), String> {
+    if prog.len() > MAX_PROGRAM {
+        return Err("pattern is too large".to_string());
+    }
+    match node {
+        Node::Class(class) => prog.push(Inst::Char(class.clone())),
+        Node::Seq(items) => {
+            for item in items {
+                compile(item, prog)?;
+            }
+        }
+        Node::Alt(alternatives) => {
+            let mut jumps = Vec::new();
+            for (i, alternative) in alternatives.iter().enumerate() {
+                if i + 1 < alternatives.len() {
+                    let split = prog.len();
+                    prog.push(Inst::Split(split + 1, 0));
+                    compile(alternative, prog)?;
+                    jumps.push(prog.len());
+                    prog.push(Inst::Jmp(0));
+                    prog[split] = Inst::Split(split + 1, prog.len());
+                } else {
+                    compile(alternative, prog)?;
+                }
+            }
+            for jump in jumps {
+                prog[jump] = Inst::Jmp(prog.len());
+            }
+        }
+        Node::Repeat {
+            node,
+            min,
+            max,
+            greedy,
+        } => {
+            let split = |body: usize, skip: usize| match greedy {
+                true => Inst::Split(body, skip),
+                false => Inst::Split(skip, body),
+            };
+            for _ in 0..*min {
+                compile(node, prog)?;
+            }
+            match max {
+                None => {
+                    let start = prog.len();
+                    prog.push(Inst::Jmp(0));
+                    compile(node, prog)?;
+                    prog.push(Inst::Jmp(start));
+                    prog[start] = split(start + 1, prog.len());
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(prog.len());
+                        prog.push(Inst::Jmp(0));
+                        compile(node, prog)?;
+                    }
+                    for at in splits {
+                        prog[at] = split(at + 1, prog.len());
+                    }
+                }
+            }
+        }
+        Node::Group(group, node) => {
+            if let Some(group) = group {
+                prog.push(Inst::Save(2 * group));
+            }
+            compile(node, prog)?;
+            if let Some(group) = group {
+                prog.push(Inst::Save(2 * group + 1));
+            }
+        }
+        Node::LineStart => prog.push(Inst::LineStart),
+        Node::LineEnd => prog.push(Inst::LineEnd),
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    prog: Vec<Inst>,
+    groups: usize,
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self, PatternError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut parser = PatternParser {
+            chars: &chars,
+            pos: 0,
+            groups: 0,
+        };
+        let node = parser.alt()?;
+        if parser.pos < chars.len() {
+            return parser.error("unmatched `)`");
+        }
+        let mut prog = Vec::new();
+        compile(&Node::Group(Some(0), Box::new(node)), &mut prog).map_err(|message| {
+            PatternError {
+                position: 0,
+                message,
+            }
+        })?;
+        prog.push(Inst::Match);
+        Ok(Pattern {
+            source: source.to_string(),
+            prog,
+            groups: parser.groups,
+        })
+    }
+
+    // Number of capture groups, not counting the whole match.
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    // The capture slots (start and end of each group) of the match at the start of `tokens`.
+    fn run(&self, tokens: &[char]) -> Option<Vec<Option<usize>>> {
+        enum Job {
+            Thread(usize, usize),
+            Restore(usize, Option<usize>),
+        }
+
+        let mut slots = vec![None; 2 * (self.groups + 1)];
+        // Bitset of (position, instruction) pairs already tried, grown as positions are reached.
+        let mut visited: Vec<u64> = Vec::new();
+        let mut jobs = vec![Job::Thread(0, 0)];
+        while let Some(job) = jobs.pop() {
+            let (mut pc, mut pos) = match job {
+                Job::Restore(slot, old) => {
+                    slots[slot] = old;
+                    continue;
+                }
+                Job::Thread(pc, pos) => (pc, pos),
+            };
+            loop {
+                let bit = pos * self.prog.len() + pc;
+                if visited.len() <= bit / 64 {
+                    visited.resize(bit / 64 + 1, 0);
+                }
+                if visited[bit / 64] & (1 << (bit % 64)) != 0 {
+                    break;
+                }
+                visited[bit / 64] |= 1 << (bit % 64);
+
+                match &self.prog[pc] {
+                    Inst::Char(class) => match tokens.get(pos) {
+                        Some(c) if class.matches(*c) => (pc, pos) = (pc + 1, pos + 1),
+                        _ => break,
+                    },
+                    Inst::Split(first, second) => {
+                        jobs.push(Job::Thread(*second, pos));
+                        pc = *first;
+                    }
+                    Inst::Jmp(to) => pc = *to,
+                    Inst::Save(slot) => {
+                        jobs.push(Job::Restore(*slot, slots[*slot]));
+                        slots[*slot] = Some(pos);
+                        pc += 1;
+                    }
+                    Inst::LineStart if pos == 0 || tokens[pos - 1] == '\n' => pc += 1,
+                    Inst::LineEnd if pos == tokens.len() || tokens[pos] == '\n' => pc += 1,
+                    Inst::LineStart | Inst::LineEnd => break,
+                    Inst::Match => return Some(slots),
+                }
+            }
+        }
+        None
+    }
+}
+
+// Compiles `source`, panicking if it isn't a valid pattern. Use `Pattern::new` to handle errors.
+pub fn pattern(source: &str) -> Pattern {
+    Pattern::new(source).unwrap_or_else(|e| panic!("invalid pattern {source:?}: {e}"))
+}
+
+// What a pattern matched: group 0 is the whole match, and groups 1 and up are the capture groups
+// in the order of their opening parentheses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    tokens: &'a [char],
+    slots: Vec<Option<usize>>,
+}
+
+impl<'a> Captures<'a> {
+    // The slice captured by group `i`, or `None` if the group didn't take part in the match.
+    pub fn get(&self, i: usize) -> Option<&'a [char]> {
+        match (self.slots.get(2 * i)?, self.slots.get(2 * i + 1)?) {
+            (Some(start), Some(end)) => Some(&self.tokens[*start..*end]),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, i: usize) -> Option<String> {
+        self.get(i).map(String::from_iter)
+    }
+
+    // Parses the text of group `i` with `FromStr`, e.g. `captures.parse::<u32>(2)`.
+    pub fn parse<N: FromStr>(&self, i: usize) -> Option<N> {
+        self.string(i)?.parse().ok()
+    }
+
+    // Number of groups, including the whole match.
+    pub fn len(&self) -> usize {
+        self.slots.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> Parser<'a, char> for Pattern {
+    type O = Captures<'a>;
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        match self.run(tokens) {
+            Some(slots) => {
+                let end = slots[1].expect("the whole match is always captured");
+                Ok((Captures { tokens, slots }, &tokens[end..]))
+            }
+            None => Err(ParseError {
+                typ: ErrorType::Msg(format!("expected /{}/", self.source)),
+                rest: tokens,
+            }),
+        }
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Special(format!("/{}/", self.source))
+    }
+}
@@ -0,0 +1,198 @@
+// Incremental parsing of `char` input read from an `io::Read`, for inputs too large to hold in
+// memory at once. Records are parsed one at a time from a buffer that is refilled from the reader
+// whenever a parser runs off the end of what has been read so far.
+
+use std::io::{self, Read};
+
+use crate::{ErrorType, ParseError, ParseResult};
+
+const CHUNK_SIZE: usize = 1 << 16;
+const DEFAULT_CAPACITY: usize = 1 << 20;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Utf8 { position: usize },
+    Parse { typ: ErrorType, position: usize },
+    // A single record needed more than `capacity` chars of lookback.
+    RecordTooLong { position: usize, capacity: usize },
+}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+pub struct CharStream<R> {
+    reader: R,
+    buf: Vec<char>,
+    // Index in `buf` of the first char not yet consumed by a record.
+    start: usize,
+    // Absolute position (in chars) of `buf[0]`.
+    offset: usize,
+    // Trailing bytes of a UTF-8 sequence split across reads.
+    pending: Vec<u8>,
+    eof: bool,
+    capacity: usize,
+}
+
+impl<R: Read> CharStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(reader, DEFAULT_CAPACITY)
+    }
+
+    // `capacity` bounds how many chars a single record may span, and so how far a parser can
+    // backtrack.
+    pub fn with_capacity(reader: R, capacity: usize) -> Self {
+        CharStream {
+            reader,
+            buf: Vec::new(),
+            start: 0,
+            offset: 0,
+            pending: Vec::new(),
+            eof: false,
+            capacity,
+        }
+    }
+
+    // Whether more input could still arrive from the reader.
+    pub fn is_partial(&self) -> bool {
+        !self.eof
+    }
+
+    // Absolute position (in chars) of the next unconsumed char.
+    pub fn position(&self) -> usize {
+        self.offset + self.start
+    }
+
+    pub fn buffered(&self) -> &[char] {
+        &self.buf[self.start..]
+    }
+
+    pub fn consume(&mut self, n: usize) {
+        self.start = (self.start + n).min(self.buf.len());
+    }
+
+    // Reads one more chunk from the reader, returning whether any new chars were buffered.
+    pub fn fill(&mut self) -> Result<bool, StreamError> {
+        if self.eof {
+            return Ok(false);
+        }
+        if self.start > 0 {
+            self.buf.drain(..self.start);
+            self.offset += self.start;
+            self.start = 0;
+        }
+
+        let mut bytes = std::mem::take(&mut self.pending);
+        let read_from = bytes.len();
+        bytes.resize(read_from + CHUNK_SIZE, 0);
+        let n = loop {
+            match self.reader.read(&mut bytes[read_from..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => break res?,
+            }
+        };
+        bytes.truncate(read_from + n);
+        if n == 0 {
+            self.eof = true;
+        }
+
+        let (valid, invalid) = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.split_at(bytes.len()),
+            Err(e) => bytes.split_at(e.valid_up_to()),
+        };
+        let valid = std::str::from_utf8(valid).expect("prefix up to valid_up_to is valid UTF-8");
+        let before = self.buf.len();
+        self.buf.extend(valid.chars());
+        if !invalid.is_empty() {
+            // Only an incomplete sequence at the very end of a read can still become valid.
+            let incomplete = std::str::from_utf8(invalid).is_err_and(|e| e.error_len().is_none());
+            if !incomplete || self.eof {
+                return Err(StreamError::Utf8 {
+                    position: self.offset + self.buf.len(),
+                });
+            }
+            self.pending = invalid.to_vec();
+        }
+        Ok(self.buf.len() > before)
+    }
+
+    // Parses the next record with `f`, reading more input for as long as the parser reaches the
+    // end of the buffered chars and more could still arrive. Returns `None` once all input has
+    // been consumed.
+    pub fn parse_next<O, F>(&mut self, f: F) -> Option<Result<O, StreamError>>
+    where
+        F: for<'b> Fn(&'b [char]) -> ParseResult<'b, char, O>,
+    {
+        loop {
+            if self.buffered().is_empty() && self.eof {
+                return None;
+            }
+            let buffered = self.buffered();
+            match f(buffered) {
+                Ok((one, rest)) if !rest.is_empty() || self.eof => {
+                    let n = buffered.len() - rest.len();
+                    self.consume(n);
+                    return Some(Ok(one));
+                }
+                // A parser can fail early because it has only seen part of a record (e.g. a
+                // keyword cut off halfway), so only report failures once no more input can arrive
+                // or the buffer is full and the failure isn't simply from running out of input.
+                Err(ParseError { typ, rest })
+                    if self.eof || (buffered.len() >= self.capacity && !rest.is_empty()) =>
+                {
+                    let position = self.position() + buffered.len() - rest.len();
+                    return Some(Err(StreamError::Parse { typ, position }));
+                }
+                _ => {}
+            }
+            if buffered.len() >= self.capacity {
+                return Some(Err(StreamError::RecordTooLong {
+                    position: self.position(),
+                    capacity: self.capacity,
+                }));
+            }
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    // An iterator over the records parsed by `f`, which ends after the first error.
+    pub fn records<O, F>(&mut self, f: F) -> Records<'_, R, F>
+    where
+        F: for<'b> Fn(&'b [char]) -> ParseResult<'b, char, O>,
+    {
+        Records {
+            stream: self,
+            f,
+            done: false,
+        }
+    }
+}
+
+pub struct Records<'s, R, F> {
+    stream: &'s mut CharStream<R>,
+    f: F,
+    done: bool,
+}
+
+impl<R: Read, O, F> Iterator for Records<'_, R, F>
+where
+    F: for<'b> Fn(&'b [char]) -> ParseResult<'b, char, O>,
+{
+    type Item = Result<O, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.stream.parse_next(&self.f);
+        if !matches!(res, Some(Ok(_))) {
+            self.done = true;
+        }
+        res
+    }
+}
@@ -0,0 +1,468 @@
+// Parsers that can also print values back out, so that a single grammar describes both directions.
+// `print` appends tokens to `out` and returns whether the value could be printed at all; a failed
+// print may leave partial output behind, which callers that try alternatives truncate away.
+
+use std::fmt::Debug;
+
+use crate::*;
+
+pub trait Syntax<'a, T: 'a>: Parser<'a, T> {
+    fn print(&self, value: &Self::O, out: &mut Vec<T>) -> bool;
+}
+
+pub fn print_to_vec<'a, T: 'a, S: Syntax<'a, T>>(syntax: &S, value: &S::O) -> Option<Vec<T>> {
+    let mut out = Vec::new();
+    syntax.print(value, &mut out).then_some(out)
+}
+
+// Test helper checking that `value` can be printed, and that parsing the printed tokens (which are
+// left in `buf`) consumes all of them and gives back `value`.
+pub fn assert_round_trip<'a, T, S>(syntax: &S, value: &S::O, buf: &'a mut Vec<T>)
+where
+    T: Debug + 'a,
+    S: Syntax<'a, T>,
+    S::O: PartialEq + Debug,
+{
+    buf.clear();
+    assert!(syntax.print(value, buf), "could not print {value:?}");
+    let printed: &'a [T] = buf;
+    match syntax.parse(printed) {
+        Ok((parsed, [])) => assert_eq!(&parsed, value, "round trip through {printed:?} changed value"),
+        Ok((_, rest)) => panic!("printed {printed:?} for {value:?}, but parsing left {rest:?}"),
+        Err(ParseError { typ, rest }) => {
+            panic!("printed {printed:?} for {value:?}, but parsing failed with {typ:?} at {rest:?}")
+        }
+    }
+}
+
+fn print_sep<'a, T: 'a, P, PS>(p: &P, sep: &PS, values: &[P::O], out: &mut Vec<T>) -> bool
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    values
+        .iter()
+        .enumerate()
+        .all(|(i, value)| (i == 0 || sep.print(&(), out)) && p.print(value, out))
+}
+
+fn print_end<'a, T: 'a, P, PS>(p: &P, sep: &PS, values: &[P::O], out: &mut Vec<T>) -> bool
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    values
+        .iter()
+        .all(|value| p.print(value, out) && sep.print(&(), out))
+}
+
+pub struct Iso<P, F, G> {
+    p: P,
+    f: F,
+    g: G,
+}
+
+impl<'a, T: 'a, O, O2, P, F, G> Parser<'a, T> for Iso<P, F, G>
+where
+    P: Parser<'a, T, O = O>,
+    F: Fn(O) -> O2,
+    G: Fn(&O2) -> Option<O>,
+{
+    type O = O2;
+
+    fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
+        let (res, rest) = self.p.parse(tokens)?;
+        Ok(((self.f)(res), rest))
+    }
+
+    fn grammar(&self) -> Grammar {
+        self.p.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out).map(&self.f)
+    }
+}
+
+impl<'a, T: 'a, O, O2, P, F, G> Syntax<'a, T> for Iso<P, F, G>
+where
+    P: Syntax<'a, T, O = O>,
+    F: Fn(O) -> O2,
+    G: Fn(&O2) -> Option<O>,
+{
+    fn print(&self, value: &O2, out: &mut Vec<T>) -> bool {
+        (self.g)(value).is_some_and(|value| self.p.print(&value, out))
+    }
+}
+
+pub struct MapPrint<P, F, G> {
+    p: P,
+    f: F,
+    g: G,
+}
+
+impl<'a, T: 'a, O, O2, P, F, G> Parser<'a, T> for MapPrint<P, F, G>
+where
+    P: Parser<'a, T, O = O>,
+    F: Fn(O) -> O2,
+    G: Fn(&O2) -> Option<Vec<T>>,
+{
+    type O = O2;
+
+    fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
+        let (res, rest) = self.p.parse(tokens)?;
+        Ok(((self.f)(res), rest))
+    }
+
+    fn grammar(&self) -> Grammar {
+        self.p.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out).map(&self.f)
+    }
+}
+
+impl<'a, T: 'a, O, O2, P, F, G> Syntax<'a, T> for MapPrint<P, F, G>
+where
+    P: Parser<'a, T, O = O>,
+    F: Fn(O) -> O2,
+    G: Fn(&O2) -> Option<Vec<T>>,
+{
+    fn print(&self, value: &O2, out: &mut Vec<T>) -> bool {
+        match (self.g)(value) {
+            Some(tokens) => {
+                out.extend(tokens);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// `map_print` that leaves the value as it is, see `Parser::print_with`.
+pub type PrintWith<P, O, G> = MapPrint<P, fn(O) -> O, G>;
+
+pub struct PrintAs<P, T> {
+    p: P,
+    tokens: Vec<T>,
+}
+
+impl<'a, T: 'a, P> Parser<'a, T> for PrintAs<P, T>
+where
+    P: Parser<'a, T>,
+{
+    type O = ();
+
+    fn parse(&self, tokens: &'a [T]) -> ParseResult<'a, T, Self::O> {
+        let (_, rest) = self.p.parse(tokens)?;
+        Ok(((), rest))
+    }
+
+    fn grammar(&self) -> Grammar {
+        self.p.grammar()
+    }
+
+    fn generate(&self, g: &mut Gen<'a, T>, out: &mut Vec<T>) -> Option<Self::O>
+    where
+        T: Clone,
+    {
+        self.p.generate(g, out).map(|_| ())
+    }
+}
+
+impl<'a, T: Clone + 'a, P> Syntax<'a, T> for PrintAs<P, T>
+where
+    P: Parser<'a, T>,
+{
+    fn print(&self, _: &(), out: &mut Vec<T>) -> bool {
+        out.extend(self.tokens.iter().cloned());
+        true
+    }
+}
+
+pub(crate) fn print_as<P, T>(p: P, tokens: impl IntoIterator<Item = T>) -> PrintAs<P, T> {
+    PrintAs {
+        p,
+        tokens: tokens.into_iter().collect(),
+    }
+}
+
+pub(crate) fn iso<P, F, G>(p: P, f: F, g: G) -> Iso<P, F, G> {
+    Iso { p, f, g }
+}
+
+pub(crate) fn map_print<P, F, G>(p: P, f: F, g: G) -> MapPrint<P, F, G> {
+    MapPrint { p, f, g }
+}
+
+impl<'a, T: 'a, O, P1, P2> Syntax<'a, T> for Disj<P1, P2>
+where
+    P1: Syntax<'a, T, O = O>,
+    P2: Syntax<'a, T, O = O>,
+{
+    fn print(&self, value: &O, out: &mut Vec<T>) -> bool {
+        let len = out.len();
+        if self.p1.print(value, out) {
+            return true;
+        }
+        out.truncate(len);
+        self.p2.print(value, out)
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Named<P> {
+    fn print(&self, value: &P::O, out: &mut Vec<T>) -> bool {
+        self.p.print(value, out)
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Backtrack<P> {
+    fn print(&self, value: &P::O, out: &mut Vec<T>) -> bool {
+        self.0.print(value, out)
+    }
+}
+
+impl<'a, T: 'a, O, P, F> Syntax<'a, T> for Satisfy<P, F>
+where
+    P: Syntax<'a, T, O = O>,
+    F: Fn(&O) -> bool,
+{
+    fn print(&self, value: &O, out: &mut Vec<T>) -> bool {
+        (self.f)(value) && self.p.print(value, out)
+    }
+}
+
+impl<'a, T: 'a, O2: Clone + PartialEq, P> Syntax<'a, T> for MapConst<P, O2>
+where
+    P: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, value: &O2, out: &mut Vec<T>) -> bool {
+        *value == self.x && self.p.print(&(), out)
+    }
+}
+
+impl<'a, T: 'a, P1, P2> Syntax<'a, T> for (P1, P2)
+where
+    P1: Syntax<'a, T>,
+    P2: Syntax<'a, T>,
+{
+    fn print(&self, (value1, value2): &Self::O, out: &mut Vec<T>) -> bool {
+        self.0.print(value1, out) && self.1.print(value2, out)
+    }
+}
+
+impl<'a, T: 'a, P1, P2> Syntax<'a, T> for Seql<P1, P2>
+where
+    P1: Syntax<'a, T>,
+    P2: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, value: &P1::O, out: &mut Vec<T>) -> bool {
+        self.p1.print(value, out) && self.p2.print(&(), out)
+    }
+}
+
+impl<'a, T: 'a, P1, P2> Syntax<'a, T> for Seqr<P1, P2>
+where
+    P1: Syntax<'a, T, O = ()>,
+    P2: Syntax<'a, T>,
+{
+    fn print(&self, value: &P2::O, out: &mut Vec<T>) -> bool {
+        self.p1.print(&(), out) && self.p2.print(value, out)
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Optional<P> {
+    fn print(&self, value: &Option<P::O>, out: &mut Vec<T>) -> bool {
+        match value {
+            Some(value) => self.0.print(value, out),
+            None => true,
+        }
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Many<P> {
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        values.iter().all(|value| self.0.print(value, out))
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Many1<P> {
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        !values.is_empty() && values.iter().all(|value| self.0.print(value, out))
+    }
+}
+
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for SepBy<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        print_sep(&self.p, &self.sep, values, out)
+    }
+}
+
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for SepBy1<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        !values.is_empty() && print_sep(&self.p, &self.sep, values, out)
+    }
+}
+
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for EndBy<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        print_end(&self.p, &self.sep, values, out)
+    }
+}
+
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for EndBy1<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        !values.is_empty() && print_end(&self.p, &self.sep, values, out)
+    }
+}
+
+// The trailing separator is optional when parsing, but printing it keeps line-based inputs in
+// their usual layout.
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for SepEndBy<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        print_end(&self.p, &self.sep, values, out)
+    }
+}
+
+impl<'a, T: 'a, P, PS> Syntax<'a, T> for SepEndBy1<P, PS>
+where
+    P: Syntax<'a, T>,
+    PS: Syntax<'a, T, O = ()>,
+{
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<T>) -> bool {
+        !values.is_empty() && print_end(&self.p, &self.sep, values, out)
+    }
+}
+
+impl<'a, T: 'a, O: Clone + PartialEq> Syntax<'a, T> for Pure<O> {
+    fn print(&self, value: &O, _: &mut Vec<T>) -> bool {
+        *value == self.0
+    }
+}
+
+impl<'a, T: 'a, O> Syntax<'a, T> for Fail<O> {
+    fn print(&self, _: &O, _: &mut Vec<T>) -> bool {
+        false
+    }
+}
+
+impl<'a, T: 'a, O> Syntax<'a, T> for FailWithMessage<O> {
+    fn print(&self, _: &O, _: &mut Vec<T>) -> bool {
+        false
+    }
+}
+
+impl<'a, T: Clone + 'a> Syntax<'a, T> for Read {
+    fn print(&self, value: &&'a T, out: &mut Vec<T>) -> bool {
+        out.push((*value).clone());
+        true
+    }
+}
+
+impl<'a, T: 'a, P: Syntax<'a, T>> Syntax<'a, T> for Choice<'_, P> {
+    fn print(&self, value: &P::O, out: &mut Vec<T>) -> bool {
+        let len = out.len();
+        for p in self.0.iter() {
+            if p.print(value, out) {
+                return true;
+            }
+            out.truncate(len);
+        }
+        false
+    }
+}
+
+impl<'a, P: Syntax<'a, char>> Syntax<'a, char> for SameIndent<P> {
+    fn print(&self, value: &P::O, out: &mut Vec<char>) -> bool {
+        out.extend(std::iter::repeat_n(' ', self.level));
+        self.p.print(value, out)
+    }
+}
+
+impl<'a, P: Syntax<'a, char>> Syntax<'a, char> for Paragraphs<P> {
+    fn print(&self, values: &Vec<P::O>, out: &mut Vec<char>) -> bool {
+        values
+            .iter()
+            .enumerate()
+            .all(|(i, value)| (i == 0 || print_blank_line(out)) && self.0.print(value, out))
+    }
+}
+
+fn print_blank_line(out: &mut Vec<char>) -> bool {
+    out.extend(['\n', '\n']);
+    true
+}
+
+macro_rules! impl_permutation_syntax {
+    ($(($P:ident, $idx:tt)),+) => {
+        impl<'a, T: 'a, $($P),+> Syntax<'a, T> for Permutation<($($P,)+)>
+        where
+            $($P: Syntax<'a, T>,)+
+        {
+            fn print(&self, value: &Self::O, out: &mut Vec<T>) -> bool {
+                $(self.0.$idx.print(&value.$idx, out))&&+
+            }
+        }
+    };
+}
+
+impl_permutation_syntax!((P1, 0));
+impl_permutation_syntax!((P1, 0), (P2, 1));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3), (P5, 4));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3), (P5, 4), (P6, 5));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3), (P5, 4), (P6, 5), (P7, 6));
+impl_permutation_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3), (P5, 4), (P6, 5), (P7, 6), (P8, 7));
+
+macro_rules! impl_sections_syntax {
+    ($(($P:ident, $idx:tt)),* ; ($PL:ident, $idxl:tt)) => {
+        impl<'a, $($P,)* $PL> Syntax<'a, char> for Sections<($($P,)* $PL,)>
+        where
+            $($P: Syntax<'a, char>,)*
+            $PL: Syntax<'a, char>,
+        {
+            fn print(&self, value: &Self::O, out: &mut Vec<char>) -> bool {
+                $(self.0.$idx.print(&value.$idx, out) && print_blank_line(out) &&)*
+                    self.0.$idxl.print(&value.$idxl, out) && {
+                        out.push('\n');
+                        true
+                    }
+            }
+        }
+    };
+}
+
+impl_sections_syntax!(; (P1, 0));
+impl_sections_syntax!((P1, 0); (P2, 1));
+impl_sections_syntax!((P1, 0), (P2, 1); (P3, 2));
+impl_sections_syntax!((P1, 0), (P2, 1), (P3, 2); (P4, 3));
+impl_sections_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3); (P5, 4));
+impl_sections_syntax!((P1, 0), (P2, 1), (P3, 2), (P4, 3), (P5, 4); (P6, 5));
@@ -0,0 +1,333 @@
+// Combinators for line-, indentation- and paragraph-structured text. Unlike the rest of the
+// crate these only make sense on `char` tokens.
+
+use crate::{ErrorType, Gen, Grammar, ParseError, ParseResult, Parser};
+
+fn is_blank(line: &[char]) -> bool {
+    line.iter().all(|c| c.is_whitespace())
+}
+
+fn line_end(tokens: &[char], start: usize) -> usize {
+    tokens[start..]
+        .iter()
+        .position(|c| *c == '\n')
+        .map_or(tokens.len(), |i| start + i)
+}
+
+fn blank_line() -> Grammar {
+    Grammar::Special("blank line".to_string())
+}
+
+fn indent(level: usize) -> Grammar {
+    Grammar::Special(format!("{level} spaces"))
+}
+
+fn indent_of(tokens: &[char]) -> usize {
+    tokens.iter().take_while(|c| **c == ' ').count()
+}
+
+// Returns the rest of `tokens` after any run of blank lines at its start.
+fn skip_blank_lines(tokens: &[char]) -> &[char] {
+    let mut next = 0;
+    while next < tokens.len() {
+        let end = line_end(tokens, next);
+        if !is_blank(&tokens[next..end]) {
+            break;
+        }
+        next = end + 1;
+    }
+    &tokens[next.min(tokens.len())..]
+}
+
+// Returns the end of the paragraph at the start of `tokens` (excluding its final newline) and the
+// start of the next one (after any run of blank lines). Lines containing only whitespace count as
+// blank; if `tokens` starts with one, the paragraph is empty.
+fn next_paragraph(tokens: &[char]) -> (usize, usize) {
+    let mut pos = 0;
+    while pos < tokens.len() {
+        let end = line_end(tokens, pos);
+        if is_blank(&tokens[pos..end]) {
+            break;
+        }
+        pos = end + 1;
+    }
+    let end = pos.saturating_sub(1).min(tokens.len());
+    let next = tokens.len() - skip_blank_lines(&tokens[end..]).len();
+    (end, next)
+}
+
+// Runs `p` on `tokens[..end]`, requiring it to consume all of it. Errors are relocated so that
+// their `rest` is a suffix of the whole of `tokens`, keeping positions comparable.
+fn parse_within<'a, P>(p: &P, tokens: &'a [char], end: usize) -> ParseResult<'a, char, P::O>
+where
+    P: Parser<'a, char>,
+{
+    let relocate = |rest: &'a [char]| &tokens[end - rest.len()..];
+    match p.parse(&tokens[..end]) {
+        Ok((res, [])) => Ok((res, &tokens[end..])),
+        Ok((_, rest)) => Err(ParseError {
+            typ: ErrorType::Msg("expected end of block".to_string()),
+            rest: relocate(rest),
+        }),
+        Err(ParseError { typ, rest }) => Err(ParseError {
+            typ,
+            rest: relocate(rest),
+        }),
+    }
+}
+
+pub struct SameIndent<P> {
+    pub(crate) level: usize,
+    pub(crate) p: P,
+}
+
+fn parse_indented<'a, P>(level: usize, p: &P, tokens: &'a [char]) -> ParseResult<'a, char, P::O>
+where
+    P: Parser<'a, char>,
+{
+    let found = indent_of(tokens);
+    if found != level {
+        return Err(ParseError {
+            typ: ErrorType::Indent {
+                expected: level,
+                found,
+            },
+            rest: &tokens[found..],
+        });
+    }
+    p.parse(&tokens[found..])
+}
+
+impl<'a, O, P> Parser<'a, char> for SameIndent<P>
+where
+    P: Parser<'a, char, O = O>,
+{
+    type O = O;
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        parse_indented(self.level, &self.p, tokens)
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::seq([indent(self.level), self.p.grammar()])
+    }
+
+    fn generate(&self, g: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        out.extend(std::iter::repeat_n(' ', self.level));
+        self.p.generate(g, out)
+    }
+}
+
+// Exactly `level` spaces of indentation, followed by `p`.
+pub fn same_indent<P>(level: usize, p: P) -> SameIndent<P> {
+    SameIndent { level, p }
+}
+
+pub struct IndentedBlock<F> {
+    enclosing: Option<usize>,
+    item: F,
+}
+
+impl<'a, O, F, P> Parser<'a, char> for IndentedBlock<F>
+where
+    F: Fn(usize) -> P,
+    P: Parser<'a, char, O = O>,
+{
+    type O = Vec<O>;
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        let level = indent_of(tokens);
+        if let Some(enclosing) = self.enclosing
+            && level <= enclosing
+        {
+            return Err(ParseError {
+                typ: ErrorType::Indent {
+                    expected: enclosing + 1,
+                    found: level,
+                },
+                rest: tokens,
+            });
+        }
+
+        let item = (self.item)(level);
+        let (first, mut rest) = parse_indented(level, &item, tokens)?;
+        let mut res = vec![first];
+        // The block continues for as long as the next line is indented by exactly `level`;
+        // anything else (a blank line, a dedent, or a deeper indent) ends it.
+        while let ['\n', next @ ..] = rest
+            && indent_of(next) == level
+            && !is_blank(&next[..line_end(next, 0)])
+        {
+            let (one, rest1) = parse_indented(level, &item, next)?;
+            rest = rest1;
+            res.push(one);
+        }
+        Ok((res, rest))
+    }
+
+    fn grammar(&self) -> Grammar {
+        let level = self.enclosing.map_or(0, |level| level + 1);
+        Grammar::repeat(
+            Grammar::seq([
+                Grammar::Special("indent".to_string()),
+                (self.item)(level).grammar(),
+            ]),
+            Some(Grammar::Literal("\n".to_string())),
+        )
+    }
+
+    fn generate(&self, g: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        let level = self.enclosing.map_or(0, |level| level + 2);
+        let item = (self.item)(level);
+        let n = g.count(1);
+        g.nested(|g| {
+            (0..n)
+                .map(|i| {
+                    if i > 0 {
+                        out.push('\n');
+                    }
+                    out.extend(std::iter::repeat_n(' ', level));
+                    item.generate(g, out)
+                })
+                .collect()
+        })
+    }
+}
+
+// One or more newline-separated items, all at the indentation of the first line, each parsed by
+// `item(level)` for that indentation. Items must not consume the newline ending their last line,
+// but may contain a `nested_block(level, ..)` of their own.
+pub fn indented_block<F>(item: F) -> IndentedBlock<F> {
+    IndentedBlock {
+        enclosing: None,
+        item,
+    }
+}
+
+// Like `indented_block`, but only matches if indented further than `enclosing`.
+pub fn nested_block<F>(enclosing: usize, item: F) -> IndentedBlock<F> {
+    IndentedBlock {
+        enclosing: Some(enclosing),
+        item,
+    }
+}
+
+pub struct Paragraphs<P>(pub(crate) P);
+
+impl<'a, O, P> Parser<'a, char> for Paragraphs<P>
+where
+    P: Parser<'a, char, O = O>,
+{
+    type O = Vec<O>;
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        let mut rest = skip_blank_lines(tokens);
+        let mut res = Vec::new();
+        while !rest.is_empty() {
+            let (end, next) = next_paragraph(rest);
+            let (one, _) = parse_within(&self.0, rest, end)?;
+            rest = &rest[next..];
+            res.push(one);
+        }
+        Ok((res, rest))
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::optional(Grammar::repeat(self.0.grammar(), Some(blank_line())))
+    }
+
+    fn generate(&self, g: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        let n = g.count(0);
+        g.nested(|g| {
+            (0..n)
+                .map(|i| {
+                    if i > 0 {
+                        out.extend(['\n', '\n']);
+                    }
+                    self.0.generate(g, out)
+                })
+                .collect()
+        })
+    }
+}
+
+// Splits the rest of the input into paragraphs separated by blank lines (ignoring any before the
+// first one), and parses each one (without its trailing newline) entirely with `p`.
+pub fn paragraphs<P>(p: P) -> Paragraphs<P> {
+    Paragraphs(p)
+}
+
+pub struct Sections<PS>(pub(crate) PS);
+
+// Splits the rest of the input on blank lines, parsing the n-th paragraph entirely with the n-th
+// parser; the last parser gets everything that is left. Sections may be empty, so blank lines at
+// the start belong to the first one rather than being skipped. Errors are wrapped in
+// `ErrorType::Section` with the (1-based) number of the section that failed.
+pub fn sections<PS>(ps: PS) -> Sections<PS> {
+    Sections(ps)
+}
+
+macro_rules! impl_sections {
+    ($(($P:ident, $O:ident, $res:ident, $idx:tt)),* ; ($PL:ident, $OL:ident, $idxl:tt)) => {
+        impl<'a, $($O, $P,)* $OL, $PL> Parser<'a, char> for Sections<($($P,)* $PL,)>
+        where
+            $($P: Parser<'a, char, O = $O>,)*
+            $PL: Parser<'a, char, O = $OL>,
+        {
+            type O = ($($O,)* $OL,);
+
+            fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+                let in_section = |n: usize| {
+                    move |ParseError { typ, rest }| ParseError {
+                        typ: ErrorType::Section(n, Box::new(typ)),
+                        rest,
+                    }
+                };
+                #[allow(unused_mut)]
+                let mut rest = tokens;
+                $(
+                    let (end, next) = next_paragraph(rest);
+                    let ($res, _) = parse_within(&self.0.$idx, rest, end).map_err(in_section($idx + 1))?;
+                    rest = &rest[next..];
+                )*
+                let end = rest.len() - rest.iter().rev().take_while(|c| **c == '\n').count();
+                let (last, _) = parse_within(&self.0.$idxl, rest, end).map_err(in_section($idxl + 1))?;
+                Ok((($($res,)* last,), &rest[rest.len()..]))
+            }
+
+            fn grammar(&self) -> Grammar {
+                Grammar::seq([$(self.0.$idx.grammar(), blank_line(),)* self.0.$idxl.grammar()])
+            }
+
+            fn generate(&self, g: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+                $(
+                    let $res = self.0.$idx.generate(g, out)?;
+                    out.extend(['\n', '\n']);
+                )*
+                let last = self.0.$idxl.generate(g, out)?;
+                out.push('\n');
+                Some(($($res,)* last,))
+            }
+        }
+    };
+}
+
+impl_sections!(; (P1, O1, 0));
+impl_sections!((P1, O1, res1, 0); (P2, O2, 1));
+impl_sections!((P1, O1, res1, 0), (P2, O2, res2, 1); (P3, O3, 2));
+impl_sections!((P1, O1, res1, 0), (P2, O2, res2, 1), (P3, O3, res3, 2); (P4, O4, 3));
+impl_sections!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3);
+    (P5, O5, 4)
+);
+impl_sections!(
+    (P1, O1, res1, 0),
+    (P2, O2, res2, 1),
+    (P3, O3, res3, 2),
+    (P4, O4, res4, 3),
+    (P5, O5, res5, 4);
+    (P6, O6, 5)
+);
@@ -0,0 +1,115 @@
+use parser_combinators::*;
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+fn chr<'a>(c: char) -> impl Parser<'a, char, O = &'a char> {
+    read().satisfy(move |x: &&char| **x == c)
+}
+
+fn digits<'a>() -> impl Parser<'a, char, O = String> {
+    read()
+        .satisfy(|c: &&char| c.is_ascii_digit())
+        .many1()
+        .map(|ds| ds.into_iter().collect())
+}
+
+// `name` followed by a number, like a `key:value` field.
+fn field<'a>(name: &'static str) -> impl Parser<'a, char, O = String> {
+    literal(name).seqr(digits())
+}
+
+// The result and the unconsumed input, or the error's position.
+fn outcome<O>(input: &[char], result: ParseResult<'_, char, O>) -> Result<(O, String), usize> {
+    result
+        .map(|(o, rest)| (o, rest.iter().collect()))
+        .map_err(|e| input.len() - e.rest.len())
+}
+
+#[test]
+fn permutation_accepts_any_order() {
+    for input in ["x1y2z3", "z3y2x1", "y2x1z3", "z3x1y2"] {
+        let input = chars(input);
+        let p = permutation((field("x"), field("y"), field("z")));
+        assert_eq!(
+            outcome(&input, p.parse(&input)),
+            Ok((("1".into(), "2".into(), "3".into()), String::new())),
+        );
+    }
+}
+
+#[test]
+fn permutation_optional_members() {
+    let cases = [
+        ("x1", Ok((("1".to_string(), None), ""))),
+        ("y2x1", Ok((("1".to_string(), Some("2".to_string())), ""))),
+        ("x1y2", Ok((("1".to_string(), Some("2".to_string())), ""))),
+        // The required member is still required.
+        ("y2", Err(2)),
+    ];
+    for (input, expected) in cases {
+        let chars = chars(input);
+        let p = permutation((field("x"), field("y").optional()));
+        let expected = expected.map(|(o, rest)| (o, rest.to_string()));
+        assert_eq!(outcome(&chars, p.parse(&chars)), expected, "{input}");
+    }
+}
+
+#[test]
+fn permutation_duplicated_members() {
+    // A member is only accepted once, so a repeat is left for whatever comes next.
+    let input = chars("x1x2");
+    let p = permutation((field("x"), field("y").optional()));
+    assert_eq!(
+        outcome(&input, p.parse(&input)),
+        Ok((("1".to_string(), None), "x2".to_string())),
+    );
+    let p = permutation((field("x"), field("y")));
+    assert_eq!(outcome(&input, p.parse(&input)), Err(2));
+}
+
+#[test]
+fn parse_iter_yields_records_lazily() {
+    let input = chars("1,22,333\n");
+    let records: Vec<_> = parse_iter(digits(), chr(',').optional(), &input)
+        .take(2)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(records, ["1", "22"]);
+
+    // A trailing separator is allowed, but anything else after a record is an error that ends
+    // the iteration.
+    let input = chars("1,2,");
+    let records: Result<Vec<_>, _> = parse_iter(digits(), chr(','), &input).collect();
+    assert_eq!(records.unwrap(), ["1", "2"]);
+    let input = chars("1,x,3");
+    let mut records = parse_iter(digits(), chr(','), &input);
+    assert_eq!(records.next().unwrap().unwrap(), "1");
+    assert_eq!(
+        input.len() - records.next().unwrap().unwrap_err().rest.len(),
+        3
+    );
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn parse_iter_stops_without_progress() {
+    let input = chars("1x2");
+    let records: Vec<_> = parse_iter(digits().optional(), chr(',').optional(), &input).collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].as_ref().unwrap().as_deref(), Some("1"));
+    assert_eq!(input.len() - records[1].as_ref().unwrap_err().rest.len(), 1);
+}
+
+#[test]
+fn try_map_rejects_like_satisfy() {
+    let input = chars("300,25");
+    let byte = || digits().try_map(|ds| ds.parse::<u8>().ok());
+    assert_eq!(outcome(&input, byte().parse(&input)), Err(3));
+    assert_eq!(
+        outcome(&input[4..], byte().parse(&input[4..])),
+        Ok((25, String::new()))
+    );
+    assert_eq!(byte().grammar(), digits().satisfy(|_| true).grammar());
+}
@@ -0,0 +1,106 @@
+use parser_combinators::*;
+
+#[derive(Clone, Debug, PartialEq, Parse)]
+#[parse(prefix = "move ")]
+struct Move {
+    #[parse(sep = " from ")]
+    count: u32,
+    #[parse(sep = " to ")]
+    from: u8,
+    to: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Parse)]
+struct Point(#[parse(sep = ",")] i64, #[parse(prefix = " ")] i64);
+
+#[derive(Clone, Debug, PartialEq, Parse)]
+#[parse(prefix = "turn ")]
+enum Switch {
+    // Unit variants are their names, and share a prefix with the next one.
+    On,
+    Off,
+    #[parse(prefix = "to ")]
+    To(u8),
+    #[parse(prefix = "dim ")]
+    Dim {
+        #[parse(sep = "%")]
+        percent: u8,
+    },
+}
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+// Parses all of `text` as a `T`, checking that printing the result gives `text` back.
+fn parse_and_print<T: FromParse + PartialEq + std::fmt::Debug>(text: &str) -> T {
+    let input = chars(text);
+    let parser = T::parser();
+    let (value, rest) = parser
+        .parse(&input)
+        .unwrap_or_else(|e| panic!("{text:?}: {}", e.typ));
+    assert!(rest.is_empty(), "{text:?} left {rest:?}");
+    let printed: String = print_to_vec(&parser, &value).unwrap().into_iter().collect();
+    assert_eq!(printed, text);
+    value
+}
+
+fn fails<T: FromParse>(text: &str) -> bool {
+    let input = chars(text);
+    !matches!(T::parser().parse(&input), Ok((_, [])))
+}
+
+#[test]
+fn struct_prefix_and_separators() {
+    assert_eq!(
+        parse_and_print::<Move>("move 3 from 1 to 20"),
+        Move {
+            count: 3,
+            from: 1,
+            to: 20
+        }
+    );
+    assert_eq!(parse_and_print::<Point>("-4, 7"), Point(-4, 7));
+    for text in ["3 from 1 to 2", "move 3 from 1 2", "move 3 from 1 to 256"] {
+        assert!(fails::<Move>(text), "{text:?}");
+    }
+    for text in ["-4,7", "-4 ,7", "-4, 7,"] {
+        assert!(fails::<Point>(text), "{text:?}");
+    }
+}
+
+#[test]
+fn enum_prefixes() {
+    assert_eq!(parse_and_print::<Switch>("turn On"), Switch::On);
+    assert_eq!(parse_and_print::<Switch>("turn Off"), Switch::Off);
+    assert_eq!(parse_and_print::<Switch>("turn to 9"), Switch::To(9));
+    assert_eq!(
+        parse_and_print::<Switch>("turn dim 40%"),
+        Switch::Dim { percent: 40 }
+    );
+    for text in ["On", "turn on", "turn to", "turn dim 40"] {
+        assert!(fails::<Switch>(text), "{text:?}");
+    }
+}
+
+#[test]
+fn derived_values_round_trip() {
+    let mut buf = Vec::new();
+    for value in [
+        Switch::On,
+        Switch::Off,
+        Switch::To(0),
+        Switch::Dim { percent: 255 },
+    ] {
+        assert_round_trip(&Switch::parser(), &value, &mut buf);
+    }
+    let moves = vec![
+        Move {
+            count: 0,
+            from: 9,
+            to: 9,
+        };
+        3
+    ];
+    assert_round_trip(&<Vec<Move>>::parser(), &moves, &mut buf);
+}
@@ -0,0 +1,69 @@
+use parser_combinators::*;
+
+fn chr<'a>(c: char) -> impl Parser<'a, char, O = &'a char> {
+    read().satisfy(move |x: &&char| **x == c)
+}
+
+fn digits<'a>() -> impl Parser<'a, char, O = Vec<&'a char>> {
+    read()
+        .satisfy(|c: &&char| c.is_ascii_digit())
+        .many1()
+        .named("digits")
+}
+
+type CharParser = for<'a> fn(&'a [char]) -> ParseResult<'a, char, ()>;
+
+#[test]
+fn ebnf_of_a_line_format() {
+    let pair = (digits(), chr('-').seqr(digits())).named("pair");
+    let p = pair.sep_end_by1(chr('\n')).seql(chr('.').optional());
+    assert_eq!(
+        p.grammar().to_ebnf("pairs"),
+        "\
+pairs  = pair, { ? token satisfying a predicate ?, pair }, [ ? token satisfying a predicate ? ], [ ? token satisfying a predicate ? ] ;
+pair   = digits, ? token satisfying a predicate ?, digits ;
+digits = ? token satisfying a predicate ?, { ? token satisfying a predicate ? } ;
+"
+    );
+}
+
+#[test]
+fn railroad_svg_has_a_diagram_per_rule() {
+    let pair = (digits(), chr('-').seqr(digits())).named("pair");
+    let svg = pair
+        .sep_end_by1(chr('\n'))
+        .grammar()
+        .to_railroad_svg("pairs");
+    assert!(svg.starts_with("<svg"), "{svg}");
+    assert!(svg.trim_end().ends_with("</svg>"), "{svg}");
+    for rule in ["pairs", "pair", "digits"] {
+        assert!(
+            svg.contains(&format!(">{rule}</text>")),
+            "{rule} missing from {svg}"
+        );
+    }
+}
+
+#[test]
+fn empty_choice_never_matches() {
+    let alternatives: [CharParser; 0] = [];
+    let p = choice(&alternatives);
+    assert_eq!(p.grammar(), Grammar::Fail);
+    assert_eq!(p.grammar().to_ebnf("none"), "none = ? nothing ? ;\n");
+    assert!(p.grammar().to_railroad_svg("none").contains("nothing"));
+    assert!(
+        Grammar::Alt(vec![])
+            .to_railroad_svg("none")
+            .contains("nothing")
+    );
+}
+
+#[test]
+fn hand_written_parsers_are_opaque() {
+    let hand_written: CharParser = |tokens| Ok(((), tokens));
+    let p = (digits(), hand_written);
+    assert!(p.grammar().has_opaque());
+    assert!(p.grammar().to_ebnf("p").contains("? hand-written parser ?"));
+    assert!(hand_written.named("nothing").grammar().has_opaque());
+    assert!(!(digits(), chr('x')).grammar().has_opaque());
+}
@@ -0,0 +1,279 @@
+use parser_combinators::check::{check, naturals, strings, vecs};
+use parser_combinators::*;
+
+const ALPHABET: &str = "ab12,\n ";
+
+fn chr<'a>(c: char) -> impl Syntax<'a, char, O = &'a char> {
+    read().satisfy(move |x: &&char| **x == c)
+}
+
+fn unit<'a>(c: char) -> impl Syntax<'a, char, O = ()> {
+    chr(c).print_as([c])
+}
+
+fn digits<'a>() -> impl Parser<'a, char, O = String> {
+    read()
+        .satisfy(|c: &&char| c.is_ascii_digit())
+        .many1()
+        .map(|ds| ds.into_iter().collect())
+}
+
+fn nat<'a>() -> impl Syntax<'a, char, O = u64> {
+    read()
+        .satisfy(|c: &&char| c.is_ascii_digit())
+        .many1()
+        .map_print(
+            |ds| {
+                ds.into_iter()
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(u64::MAX)
+            },
+            |n: &u64| Some(n.to_string().chars().collect()),
+        )
+}
+
+fn word<'a>(w: &'static str) -> impl Parser<'a, char, O = String> {
+    move |tokens: &'a [char]| {
+        let w: Vec<char> = w.chars().collect();
+        if tokens.starts_with(&w) {
+            Ok((w.iter().collect(), &tokens[w.len()..]))
+        } else {
+            Err(ParseError {
+                typ: ErrorType::Msg("word".to_string()),
+                rest: tokens,
+            })
+        }
+    }
+}
+
+// What a parse did, comparable across parsers: the result and how much input was left, or how
+// much input was left where it failed.
+fn outcome<O>(res: ParseResult<'_, char, O>) -> Result<(O, usize), usize> {
+    res.map(|(res, rest)| (res, rest.len()))
+        .map_err(|e| e.rest.len())
+}
+
+// A nameable parser type, for `choice` over alternatives built from different combinators.
+type StringParser = fn(&[char]) -> ParseResult<'_, char, String>;
+
+fn join(ns: &[u64], sep: char) -> Vec<char> {
+    ns.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(&sep.to_string())
+        .chars()
+        .collect()
+}
+
+#[test]
+fn map_identity() {
+    check("map identity", strings(ALPHABET), |input| {
+        outcome(digits().map(|x| x).parse(input)) == outcome(digits().parse(input))
+            && outcome(digits().sep_by(chr(',')).map(|x| x).parse(input))
+                == outcome(digits().sep_by(chr(',')).parse(input))
+    });
+}
+
+#[test]
+fn map_composition() {
+    let f = |s: String| s.len();
+    let g = |n: usize| n * 2 + 1;
+    check("map composition", strings(ALPHABET), |input| {
+        outcome(digits().map(f).map(g).parse(input))
+            == outcome(digits().map(|x| g(f(x))).parse(input))
+    });
+}
+
+#[test]
+fn pure_is_identity_for_seq() {
+    check("pure seqr/seql identity", strings(ALPHABET), |input| {
+        outcome(pure(()).seqr(digits()).parse(input)) == outcome(digits().parse(input))
+            && outcome(digits().seql(pure(())).parse(input)) == outcome(digits().parse(input))
+            && outcome(Parser::<char>::parse(&pure(7), input)) == Ok((7, input.len()))
+    });
+}
+
+#[test]
+fn disj_is_associative() {
+    check("disj associativity", strings(ALPHABET), |input| {
+        let b = || chr('a').map(|c| c.to_string());
+        outcome(word("ab").disj(b()).disj(digits()).parse(input))
+            == outcome(word("ab").disj(b().disj(digits())).parse(input))
+    });
+}
+
+#[test]
+fn sep_by_agrees_with_sep_end_by_without_trailing_separator() {
+    let strategy = (vecs(naturals(1000)), strings("ab \n1"));
+    check("sep_by ~ sep_end_by", strategy, |(ns, suffix)| {
+        let mut input = join(ns, ',');
+        input.extend(suffix);
+        let res = outcome(nat().sep_by(chr(',')).parse(&input));
+        res == outcome(nat().sep_end_by(chr(',')).parse(&input))
+            && (suffix.first().is_some_and(char::is_ascii_digit)
+                || res == Ok((ns.clone(), suffix.len())))
+    });
+}
+
+#[test]
+fn parse_iter_agrees_with_sep_end_by() {
+    check("parse_iter ~ sep_end_by", vecs(naturals(1000)), |ns| {
+        let input: Vec<char> = ns
+            .iter()
+            .flat_map(|n| format!("{n}\n").chars().collect::<Vec<_>>())
+            .collect();
+        let streamed: Result<Vec<_>, _> = parse_iter(nat(), chr('\n'), &input).collect();
+        streamed.ok().as_ref() == Some(ns)
+            && outcome(nat().sep_end_by(chr('\n')).parse(&input)) == Ok((ns.clone(), 0))
+    });
+}
+
+fn none_leaves_input<O>(res: ParseResult<'_, char, Option<O>>, input: &[char]) -> bool {
+    match res {
+        Ok((None, rest)) => rest.len() == input.len(),
+        Ok((Some(_), _)) => true,
+        Err(_) => false,
+    }
+}
+
+#[test]
+fn optional_never_fails() {
+    check("optional never fails", strings(ALPHABET), |input| {
+        none_leaves_input(digits().optional().parse(input), input)
+            && none_leaves_input(chr('a').many1().optional().parse(input), input)
+            && none_leaves_input(
+                Parser::<char>::optional(fail::<()>(ErrorType::Read)).parse(input),
+                input,
+            )
+            && none_leaves_input(digits().sep_by1(chr(',')).optional().parse(input), input)
+    });
+}
+
+#[test]
+fn optional_samples_never_fail() {
+    check("optional samples never fail", naturals(u64::MAX), |seed| {
+        let parser = Parser::<char>::optional(fail::<()>(ErrorType::Read));
+        sample(&parser, &mut Rng::new(*seed), 8).is_some_and(|input| input.is_empty())
+    });
+}
+
+#[test]
+fn samples_are_accepted() {
+    check("samples parse", naturals(u64::MAX), |seed| {
+        let parser = || nat().sep_end_by1(unit('\n'));
+        let input = sample(&parser(), &mut Rng::new(*seed), 8).unwrap();
+        matches!(outcome(parser().parse(&input)), Ok((_, 0)))
+    });
+}
+
+#[test]
+fn print_round_trips() {
+    check("print round trip", vecs(naturals(1 << 40)), |ns| {
+        let mut buf = Vec::new();
+        assert_round_trip(&nat().sep_by(unit(',')), ns, &mut buf);
+        let mut buf = Vec::new();
+        assert_round_trip(&nat().sep_end_by(unit('\n')), ns, &mut buf);
+        true
+    });
+}
+
+#[test]
+fn from_parse_round_trips() {
+    let strategy = (vecs((naturals(1000), naturals(1000))), vecs(naturals(1)));
+    check("FromParse round trip", strategy, |(pairs, bits)| {
+        let rows: Vec<[u64; 2]> = pairs.iter().map(|(a, b)| [*a, *b]).collect();
+        let flags: Vec<bool> = bits.iter().map(|b| *b == 1).collect();
+        let mut buf = Vec::new();
+        assert_round_trip(
+            &<(Vec<[u64; 2]>, Vec<bool>)>::parser(),
+            &(rows, flags),
+            &mut buf,
+        );
+        true
+    });
+}
+
+#[test]
+fn empty_input() {
+    let alternatives: [StringParser; 2] = [|t| digits().parse(t), |t| word("x").parse(t)];
+    let empty: &[char] = &[];
+    assert_eq!(outcome(choice(&alternatives).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(Parser::<char>::parse(&read(), empty)).map(|_| ()),
+        Err(0)
+    );
+    assert_eq!(outcome(Parser::<char>::parse(&pure(1), empty)), Ok((1, 0)));
+    assert_eq!(
+        outcome(Parser::<char>::parse(&pure_with(|| 2), empty)),
+        Ok((2, 0))
+    );
+    assert_eq!(
+        outcome(Parser::<char>::parse(&fail::<()>(ErrorType::Read), empty)),
+        Err(0)
+    );
+    assert_eq!(
+        outcome(Parser::<char>::parse(
+            &fail_with_message::<()>("no".to_string()),
+            empty
+        )),
+        Err(0)
+    );
+    assert_eq!(outcome(chr('a').map(|c| *c).parse(empty)), Err(0));
+    assert_eq!(outcome(chr('a').map_const(1).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(chr('a').backtrack().map(|c| *c).parse(empty)),
+        Err(0)
+    );
+    assert_eq!(
+        outcome(chr('a').named("a").map(|c| *c).parse(empty)),
+        Err(0)
+    );
+    assert_eq!(outcome(digits().disj(word("x")).parse(empty)), Err(0));
+    assert_eq!(outcome((digits(), digits()).parse(empty)), Err(0));
+    assert_eq!(outcome(digits().seql(digits()).parse(empty)), Err(0));
+    assert_eq!(outcome(digits().seqr(digits()).parse(empty)), Err(0));
+    assert_eq!(outcome(digits().optional().parse(empty)), Ok((None, 0)));
+    assert_eq!(outcome(digits().many().parse(empty)), Ok((vec![], 0)));
+    assert_eq!(outcome(digits().many1().parse(empty)), Err(0));
+    assert_eq!(
+        outcome(digits().sep_by(chr(',')).parse(empty)),
+        Ok((vec![], 0))
+    );
+    assert_eq!(outcome(digits().sep_by1(chr(',')).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(digits().end_by(chr(',')).parse(empty)),
+        Ok((vec![], 0))
+    );
+    assert_eq!(outcome(digits().end_by1(chr(',')).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(digits().sep_end_by(chr(',')).parse(empty)),
+        Ok((vec![], 0))
+    );
+    assert_eq!(outcome(digits().sep_end_by1(chr(',')).parse(empty)), Err(0));
+    assert_eq!(outcome(digits().satisfy(|_| true).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(nat().iso(|n| n + 1, |n| n.checked_sub(1)).parse(empty)),
+        Err(0)
+    );
+    assert_eq!(outcome(digits().print_as(['0']).parse(empty)), Err(0));
+    assert_eq!(
+        outcome(permutation((digits().optional(), word("x").optional())).parse(empty)),
+        Ok(((None, None), 0))
+    );
+    assert_eq!(
+        outcome(permutation((digits(), word("x"))).parse(empty)),
+        Err(0)
+    );
+    assert_eq!(outcome(paragraphs(digits()).parse(empty)), Ok((vec![], 0)));
+    assert_eq!(outcome(sections((digits(), digits())).parse(empty)), Err(0));
+    assert_eq!(outcome(indented_block(|_| digits()).parse(empty)), Err(0));
+    assert_eq!(outcome(same_indent(0, digits()).parse(empty)), Err(0));
+    assert_eq!(parse_iter(digits(), chr(','), empty).count(), 0);
+    assert!(
+        CharStream::new(&b""[..])
+            .records(|t| digits().parse(t))
+            .next()
+            .is_none()
+    );
+}
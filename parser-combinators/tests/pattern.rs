@@ -0,0 +1,113 @@
+use parser_combinators::*;
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+// The whole match, or `None` if the pattern doesn't match.
+fn matched(source: &str, input: &str) -> Option<String> {
+    let input = chars(input);
+    let (captures, _) = pattern(source).parse(&input).ok()?;
+    captures.string(0)
+}
+
+#[test]
+fn matches_at_the_start() {
+    let cases: &[(&str, &str, Option<&str>)] = &[
+        ("abc", "abcd", Some("abc")),
+        ("abc", "xabc", None),
+        ("a.c", "a-c", Some("a-c")),
+        ("a.c", "a\nc", None),
+        (r"\d+", "123,4", Some("123")),
+        (r"\d*", "x", Some("")),
+        (r"[LR]\d+", "R42\nL7", Some("R42")),
+        (r"[^,]+", "ab c,d", Some("ab c")),
+        ("[a-c_-]+", "b_-ad", Some("b_-a")),
+        (r"[]\]]+", "]]x", Some("]]")),
+        (r"\w+\s\S", "foo_1 !", Some("foo_1 !")),
+        ("a{2}", "aaa", Some("aa")),
+        ("a{2,}", "aaaa", Some("aaaa")),
+        ("a{1,3}", "aaaa", Some("aaa")),
+        ("a{2,3}", "a", None),
+        ("a+?", "aaa", Some("a")),
+        ("a*?b", "aab", Some("aab")),
+        ("cat|category", "category", Some("cat")),
+        ("(?:cat|category)s", "categorys", Some("categorys")),
+        ("colou?r", "color", Some("color")),
+        (r"\d+$", "12\n3", Some("12")),
+        (r"\d+$", "12a", None),
+        (r"a\n^b", "a\nb", Some("a\nb")),
+        (r"\.\*\\", r".*\", Some(r".*\")),
+        ("", "abc", Some("")),
+    ];
+    for (source, input, expected) in cases {
+        let whole = matched(source, input);
+        assert_eq!(whole.as_deref(), *expected, "/{source}/ on {input:?}");
+    }
+}
+
+#[test]
+fn captures_groups() {
+    let input = chars("move 3 from 1 to 2\n");
+    let p = pattern(r"move (\d+) from (\d+) to (\d+)");
+    assert_eq!(p.groups(), 3);
+    let (captures, rest) = p.parse(&input).unwrap();
+    assert_eq!(captures.len(), 4);
+    assert_eq!(captures.parse::<u32>(1), Some(3));
+    assert_eq!(captures.parse::<u32>(2), Some(1));
+    assert_eq!(captures.get(3), Some(&['2'][..]));
+    assert_eq!(captures.get(4), None);
+    assert_eq!(rest, ['\n']);
+
+    // Groups that don't take part in the match capture nothing, and repeated groups capture
+    // their last repetition.
+    let input = chars("b12");
+    let (captures, _) = pattern(r"(a)?(b)(\d)+").parse(&input).unwrap();
+    assert_eq!(captures.get(1), None);
+    assert_eq!(captures.string(2).as_deref(), Some("b"));
+    assert_eq!(captures.string(3).as_deref(), Some("2"));
+}
+
+#[test]
+fn composes_with_combinators() {
+    let input = chars("L68\nR30\nL5");
+    let rotation = pattern(r"([LR])(\d+)").map(|c| {
+        let sign = if c.get(1) == Some(&['L'][..]) { -1 } else { 1 };
+        sign * c.parse::<i64>(2).unwrap()
+    });
+    let (turns, rest) = rotation.sep_by1(pattern("\n")).parse(&input).unwrap();
+    assert_eq!(turns, vec![-68, 30, -5]);
+    assert!(rest.is_empty());
+
+    // A failing pattern consumes nothing, so it can be the first branch of a `disj`.
+    let input = chars("x");
+    let p = pattern("a+b").map(|_| 1).disj(pattern("x").map(|_| 2));
+    assert_eq!(p.parse(&input).unwrap().0, 2);
+}
+
+#[test]
+fn nested_repetition_is_linear() {
+    let input = chars(&"a".repeat(10_000));
+    assert!(pattern("(a*)*b").parse(&input).is_err());
+    assert!(pattern("(a|aa)+$").parse(&input).is_ok());
+}
+
+#[test]
+fn rejects_invalid_patterns() {
+    let cases: &[(&str, usize)] = &[
+        ("(ab", 3),
+        ("ab)", 2),
+        ("*a", 0),
+        ("a**", 2),
+        ("[a-", 3),
+        ("[z-a]", 4),
+        (r"\q", 1),
+        ("a{3,1}", 1),
+        ("a{x}", 2),
+        ("(?a)", 2),
+    ];
+    for (source, position) in cases {
+        let err = Pattern::new(source).expect_err(source);
+        assert_eq!(err.position, *position, "{source:?}: {err}");
+    }
+}
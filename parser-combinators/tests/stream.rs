@@ -0,0 +1,119 @@
+use std::io::{self, Read};
+
+use parser_combinators::*;
+
+// A reader that hands out at most `chunk` bytes per read, and is interrupted before each one.
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    chunk: usize,
+    interrupt: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = self.chunk.min(buf.len()).min(self.bytes.len());
+        buf[..n].copy_from_slice(&self.bytes[..n]);
+        self.bytes = &self.bytes[n..];
+        Ok(n)
+    }
+}
+
+fn trickle(s: &[u8], chunk: usize) -> Trickle<'_> {
+    Trickle {
+        bytes: s,
+        chunk,
+        interrupt: false,
+    }
+}
+
+// A line of anything but newlines, with its newline.
+fn line(tokens: &[char]) -> ParseResult<'_, char, String> {
+    read()
+        .satisfy(|c: &&char| **c != '\n')
+        .many1()
+        .seql(read().satisfy(|c: &&char| **c == '\n'))
+        .map(|cs| cs.into_iter().collect())
+        .parse(tokens)
+}
+
+fn lines(input: &[u8], chunk: usize, capacity: usize) -> Vec<Result<String, StreamError>> {
+    CharStream::with_capacity(trickle(input, chunk), capacity)
+        .records(line)
+        .collect()
+}
+
+#[test]
+fn refills_between_and_within_records() {
+    let input = b"one\ntwo\nthree\nfour\n";
+    for chunk in [1, 2, 3, 5, 64] {
+        let records: Vec<_> = lines(input, chunk, 64)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records, ["one", "two", "three", "four"], "chunk {chunk}");
+    }
+}
+
+#[test]
+fn utf8_split_across_reads() {
+    let input = "żółw\n€\n🦀🦀\n";
+    for chunk in [1, 2, 3] {
+        let records: Vec<_> = lines(input.as_bytes(), chunk, 64)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records, ["żółw", "€", "🦀🦀"], "chunk {chunk}");
+    }
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    // A byte that can never start a sequence, and a sequence cut off by the end of the input.
+    // The record before the bad bytes isn't known to be complete until the next char arrives,
+    // so it's the error that ends the iteration.
+    for (input, position) in [(&b"ab\ncd\n\xffe\n"[..], 6), (&b"ab\ncd\n\xe2\x82"[..], 6)] {
+        let records = lines(input, 1, 64);
+        assert_eq!(records.len(), 2, "{input:?}");
+        assert_eq!(records[0].as_deref().unwrap(), "ab");
+        assert!(
+            matches!(records[1], Err(StreamError::Utf8 { position: p }) if p == position),
+            "{input:?}: {:?}",
+            records[1]
+        );
+    }
+}
+
+#[test]
+fn lookback_is_bounded_by_the_capacity() {
+    let input = b"short\nmuch too long\nshort\n";
+    let records = lines(input, 2, 8);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].as_deref().unwrap(), "short");
+    assert!(
+        matches!(
+            records[1],
+            Err(StreamError::RecordTooLong {
+                position: 6,
+                capacity: 8
+            })
+        ),
+        "{:?}",
+        records[1]
+    );
+}
+
+#[test]
+fn parse_errors_have_absolute_positions() {
+    let input = b"one\ntwo\n\nthree\n";
+    let records = lines(input, 1, 64);
+    assert_eq!(records.len(), 3);
+    assert!(
+        matches!(records[2], Err(StreamError::Parse { position: 9, .. })),
+        "{:?}",
+        records[2]
+    );
+}
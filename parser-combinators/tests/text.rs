@@ -0,0 +1,149 @@
+use parser_combinators::*;
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+fn chr<'a>(c: char) -> impl Parser<'a, char, O = &'a char> {
+    read().satisfy(move |x: &&char| **x == c)
+}
+
+fn digits<'a>() -> impl Parser<'a, char, O = String> {
+    read()
+        .satisfy(|c: &&char| c.is_ascii_digit())
+        .many1()
+        .map(|ds| ds.into_iter().collect())
+}
+
+fn lines<'a>() -> impl Parser<'a, char, O = Vec<String>> {
+    digits().sep_by1(chr('\n'))
+}
+
+// The result and the unconsumed input, or the error's position.
+fn outcome<O>(input: &[char], result: ParseResult<'_, char, O>) -> Result<(O, String), usize> {
+    result
+        .map(|(o, rest)| (o, rest.iter().collect()))
+        .map_err(|e| input.len() - e.rest.len())
+}
+
+fn strings(ss: &[&str]) -> Vec<String> {
+    ss.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn paragraphs_split_on_blank_lines() {
+    let cases = [
+        ("1\n2\n\n3\n", vec![strings(&["1", "2"]), strings(&["3"])]),
+        ("1\n \n\t\n2", vec![strings(&["1"]), strings(&["2"])]),
+        // Blank lines before the first paragraph don't make an empty one.
+        ("\n  \n1\n\n2\n", vec![strings(&["1"]), strings(&["2"])]),
+        ("\n\n", vec![]),
+    ];
+    for (input, expected) in cases {
+        let chars = chars(input);
+        let p = paragraphs(lines());
+        assert_eq!(
+            outcome(&chars, p.parse(&chars)),
+            Ok((expected, String::new())),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn paragraphs_must_be_parsed_entirely() {
+    let input = chars("1\n\n2\nx\n\n3");
+    let p = paragraphs(lines());
+    assert_eq!(outcome(&input, p.parse(&input)), Err(4));
+}
+
+#[test]
+fn sections_parse_each_paragraph_with_its_own_parser() {
+    let input = chars("1\n\n2\n3\n\n\n");
+    let p = sections((digits(), lines()));
+    assert_eq!(
+        outcome(&input, p.parse(&input)),
+        Ok((("1".to_string(), strings(&["2", "3"])), String::new())),
+    );
+}
+
+#[test]
+fn sections_say_which_section_failed() {
+    for (input, section, position) in [("x\n\n2", 1, 1), ("1\n\n2\nx", 2, 4), ("1", 2, 1)] {
+        let chars = chars(input);
+        let p = sections((digits(), lines()));
+        let err = p.parse(&chars).unwrap_err();
+        assert!(
+            matches!(err.typ, ErrorType::Section(n, _) if n == section),
+            "{input:?}: {}",
+            err.typ
+        );
+        assert_eq!(chars.len() - err.rest.len(), position, "{input:?}");
+    }
+}
+
+#[test]
+fn indented_block_ends_at_another_indentation() {
+    let cases = [
+        ("  1\n  2\n3", Ok((strings(&["1", "2"]), "\n3"))),
+        ("1\n2\n\n3", Ok((strings(&["1", "2"]), "\n\n3"))),
+        ("  1\n    2", Ok((strings(&["1"]), "\n    2"))),
+        // An item at the block's indentation must parse.
+        ("  1\n  x", Err(7)),
+    ];
+    for (input, expected) in cases {
+        let chars = chars(input);
+        let p = indented_block(|_| digits());
+        let expected = expected.map(|(o, rest)| (o, rest.to_string()));
+        assert_eq!(outcome(&chars, p.parse(&chars)), expected, "{input:?}");
+    }
+}
+
+#[test]
+fn nested_blocks_are_indented_further() {
+    let input = chars("1\n  2\n  3\n4\n5\n  6");
+    let p = indented_block(|level| {
+        (
+            digits(),
+            chr('\n').seqr(nested_block(level, |_| digits())).optional(),
+        )
+    });
+    let expected = vec![
+        ("1".to_string(), Some(strings(&["2", "3"]))),
+        ("4".to_string(), None),
+        ("5".to_string(), Some(strings(&["6"]))),
+    ];
+    assert_eq!(
+        outcome(&input, p.parse(&input)),
+        Ok((expected, String::new()))
+    );
+
+    let input = chars("  1");
+    let err = nested_block(2, |_| digits()).parse(&input).unwrap_err();
+    assert!(matches!(
+        err.typ,
+        ErrorType::Indent {
+            expected: 3,
+            found: 2
+        }
+    ));
+}
+
+#[test]
+fn same_indent_needs_exactly_that_indentation() {
+    let input = chars("  12");
+    assert_eq!(
+        outcome(&input, same_indent(2, digits()).parse(&input)),
+        Ok(("12".to_string(), String::new()))
+    );
+    for (input, found) in [(" 12", 1), ("   12", 3)] {
+        let chars = chars(input);
+        let err = same_indent(2, digits()).parse(&chars).unwrap_err();
+        assert!(
+            matches!(err.typ, ErrorType::Indent { expected: 2, found: f } if f == found),
+            "{input:?}: {}",
+            err.typ
+        );
+        assert_eq!(chars.len() - err.rest.len(), found);
+    }
+}
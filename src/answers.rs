@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use parser_combinators::{Parser, pattern};
+
+use crate::{AocError, Part, parser_utils::{chr, try_parse_all}};
+
+pub const DEFAULT_ANSWERS : &str = "inputs/answers.toml";
+
+// The accepted answers for each day, input file and part, kept in a small subset of TOML:
+//
+//     [day1."inputs/day1.txt"]
+//     part1 = 1055
+//     part2 = "6386"
+//
+// Answers are integers or strings (without escapes), and `#` starts a comment. Answers and input
+// paths with quotes or newlines can't be written without escapes, so `save` refuses them.
+#[derive(Default)]
+pub struct Answers {
+    answers : BTreeMap<(i32, String, u8), String>,
+}
+
+enum Line {
+    Header(i32, String),
+    Answer(u8, String),
+    Blank,
+}
+
+impl Answers {
+    // No answers if there's no file at `path` yet.
+    pub fn load(path : &str) -> Result<Self, AocError> {
+        if !Path::new(path).exists() {
+            return Ok(Answers::default());
+        }
+        let raw_chars : Vec<char> = fs::read_to_string(path)
+            .map_err(|source| AocError::Io { path: path.to_string(), source })?
+            .chars().collect();
+
+        let header = pattern(r#"\[day(\d{1,2})\."([^"\n]*)"\]"#)
+            .map(|c| Line::Header(c.parse(1).expect("at most two digits"), c.string(2).expect("group 2 always matches")));
+        let answer = pattern(r#"part([12])[ \t]*=[ \t]*(?:(\d+)|"([^"\n]*)")"#)
+            .map(|c| Line::Answer(c.parse(1).expect("1 or 2"), c.string(2).or(c.string(3)).expect("one of the values matches")));
+        let line = pattern("[ \t]*")
+            .seqr(header.disj(answer).optional())
+            .seql(pattern("[ \t]*(?:#[^\n]*)?"))
+            .map(|line| line.unwrap_or(Line::Blank));
+        let lines = try_parse_all(line.sep_by(chr('\n')), &raw_chars).map_err(|err| AocError::parse(path, &raw_chars, err))?;
+
+        let mut answers = Answers::default();
+        let mut table = None;
+        for (i, line) in lines.into_iter().enumerate() {
+            match line {
+                Line::Header(day, input) => table = Some((day, input)),
+                Line::Answer(part, answer) => {
+                    let Some((day, input)) = &table else {
+                        return Err(AocError::Validation(format!("{path}:{}: answer before any [dayN.\"input\"] header", i + 1)));
+                    };
+                    answers.answers.insert((*day, input.clone(), part), answer);
+                }
+                Line::Blank => {}
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn save(&self, path : &str) -> Result<(), AocError> {
+        let mut text = String::from("# Accepted answers, checked by `run`, `test` and `all`. Update them with `--record`.\n");
+        let mut table = None;
+        for ((day, input, part), answer) in &self.answers {
+            if let Some(text) = [input, answer].into_iter().find(|text| text.contains(['"', '\n'])) {
+                return Err(AocError::Validation(format!("{path}: can't save {text:?} for day {day} part {part}, it has a quote or newline")));
+            }
+            if table != Some((day, input)) {
+                text.push_str(&format!("\n[day{day}.\"{input}\"]\n"));
+                table = Some((day, input));
+            }
+            if !answer.is_empty() && answer.chars().all(|c| c.is_ascii_digit()) {
+                text.push_str(&format!("part{part} = {answer}\n"));
+            } else {
+                text.push_str(&format!("part{part} = \"{answer}\"\n"));
+            }
+        }
+        fs::write(path, text).map_err(|source| AocError::Io { path: path.to_string(), source })
+    }
+
+    pub fn get(&self, day : i32, input : &str, part : Part) -> Option<&str> {
+        self.answers.get(&(day, input.to_string(), part.number())).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day : i32, input : &str, part : Part, answer : String) {
+        self.answers.insert((day, input.to_string(), part.number()), answer);
+    }
+
+    // Whether `answer` is the accepted one, or `None` if there isn't one.
+    pub fn check(&self, day : i32, input : &str, part : Part, answer : &str) -> Option<bool> {
+        self.get(day, input, part).map(|expected| expected == answer)
+    }
+
+    // A mark to show after `answer`: ✓, or ✗ with the accepted answer, or nothing.
+    pub fn mark(&self, day : i32, input : &str, part : Part, answer : &str) -> String {
+        match self.get(day, input, part) {
+            None => String::new(),
+            Some(expected) if expected == answer => " ✓".to_string(),
+            Some(expected) => format!(" ✗ (expected {expected})"),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{collections::BTreeMap, fs, path::Path, time::{Duration, Instant}};
+
+use parser_combinators::{Parser, pattern};
+
+use crate::{AocError, DynDay, Part, load_input, millis, parser_utils::{chr, try_parse_all}};
+
+// Where `bench --save-baseline` writes to and `bench` compares against by default. Timings only
+// mean something on the machine that made them, so this lives under `target/`.
+pub const DEFAULT_BASELINE : &str = "target/bench/baseline.txt";
+
+// Slowdowns smaller than this are noise, however large they are relative to the baseline.
+const MIN_REGRESSION : Duration = Duration::from_micros(50);
+
+pub const PHASES : [&str; 6] = ["read", "chars", "parse1", "solve1", "parse2", "solve2"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub samples : usize,
+    pub min : Duration,
+    pub median : Duration,
+    pub mean : Duration,
+    pub stddev : Duration,
+    // Samples outside Tukey's fences, 1.5 interquartile ranges beyond the quartiles.
+    pub outliers : usize,
+}
+
+impl Stats {
+    pub fn new(samples : &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "stats need at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let quantile = |q : f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize].as_secs_f64();
+        let (q1, q3) = (quantile(0.25), quantile(0.75));
+        let (low, high) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+
+        let secs : Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+        Stats {
+            samples: sorted.len(),
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: secs.iter().filter(|s| **s < low || **s > high).count(),
+        }
+    }
+}
+
+// One run of every phase: reading and converting the input, then parsing and solving each part
+// with its own parser.
+fn iteration(day : &dyn DynDay, path : &str) -> Result<[Duration; 6], AocError> {
+    let (raw_chars, times) = load_input(path)?;
+    let easy = day.solve_part(Part::Easy, path, &raw_chars)?;
+    let hard = day.solve_part(Part::Hard, path, &raw_chars)?;
+    Ok([times.read, times.chars, easy.parse_time, easy.solve_time, hard.parse_time, hard.solve_time])
+}
+
+// Times each phase of `day` on the input at `path`, after `warmup` untimed iterations. A time
+// budget always gets at least one iteration.
+pub fn bench_day(day : &dyn DynDay, path : &str, budget : Budget, warmup : usize) -> Result<[Stats; 6], AocError> {
+    for _ in 0..warmup {
+        iteration(day, path)?;
+    }
+    let mut samples : [Vec<Duration>; 6] = Default::default();
+    let start = Instant::now();
+    loop {
+        for (phase, time) in samples.iter_mut().zip(iteration(day, path)?) {
+            phase.push(time);
+        }
+        let done = match budget {
+            Budget::Iterations(n) => samples[0].len() >= n,
+            Budget::Time(limit) => start.elapsed() >= limit,
+        };
+        if done {
+            break;
+        }
+    }
+    Ok(samples.map(|phase| Stats::new(&phase)))
+}
+
+// Median times from an earlier run, one `<day> <phase> <nanoseconds>` line per phase.
+#[derive(Default)]
+pub struct Baseline {
+    medians : BTreeMap<(i32, String), Duration>,
+}
+
+impl Baseline {
+    // An empty baseline if there's no file at `path` yet.
+    pub fn load(path : &str) -> Result<Self, AocError> {
+        if !Path::new(path).exists() {
+            return Ok(Baseline::default());
+        }
+        let raw_chars : Vec<char> = fs::read_to_string(path)
+            .map_err(|source| AocError::Io { path: path.to_string(), source })?
+            .chars().collect();
+        let line = pattern(r"(\d+) (\w+) (\d+)").map(|c| {
+            let day = c.parse::<i32>(1).ok_or("day number out of range")?;
+            let nanos = c.parse::<u64>(3).ok_or("time out of range")?;
+            Ok(((day, c.string(2).expect("group 2 always matches")), Duration::from_nanos(nanos)))
+        });
+        let lines = try_parse_all(line.sep_end_by(chr('\n')), &raw_chars).map_err(|err| AocError::parse(path, &raw_chars, err))?;
+        let medians = lines.into_iter().collect::<Result<_, &str>>()
+            .map_err(|message| AocError::Validation(format!("{path}: {message}")))?;
+        Ok(Baseline { medians })
+    }
+
+    pub fn save(&self, path : &str) -> Result<(), AocError> {
+        let text : String = self.medians.iter()
+            .map(|((day, phase), median)| format!("{day} {phase} {}\n", median.as_nanos()))
+            .collect();
+        let io_error = |source| AocError::Io { path: path.to_string(), source };
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(path, text).map_err(io_error)
+    }
+
+    pub fn get(&self, day : i32, phase : &str) -> Option<Duration> {
+        self.medians.get(&(day, phase.to_string())).copied()
+    }
+
+    pub fn set(&mut self, day : i32, phase : &str, median : Duration) {
+        self.medians.insert((day, phase.to_string()), median);
+    }
+}
+
+// How much slower `stats` is than `baseline`, as a fraction, if that's more than `threshold` and
+// more than noise.
+pub fn regression(stats : &Stats, baseline : Duration, threshold : f64) -> Option<f64> {
+    let change = stats.median.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+    (change > threshold && stats.median > baseline + MIN_REGRESSION).then_some(change)
+}
+
+// A table of `stats` for `day`, compared against `baseline`, and the number of regressions in it.
+pub fn report(day : i32, stats : &[Stats; 6], baseline : &Baseline, threshold : f64) -> (String, usize) {
+    let mut lines = vec![format!(
+        "day {day:<3} {:>7}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}  vs baseline",
+        "samples", "min ms", "median ms", "mean ms", "stddev ms", "outliers",
+    )];
+    let mut regressions = 0;
+    for (phase, stats) in PHASES.iter().zip(stats) {
+        let comparison = match baseline.get(day, phase) {
+            None => String::new(),
+            Some(base) => {
+                let change = (stats.median.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0;
+                match regression(stats, base, threshold) {
+                    Some(_) => {
+                        regressions += 1;
+                        format!("{change:+.1}% REGRESSION")
+                    }
+                    None => format!("{change:+.1}%"),
+                }
+            }
+        };
+        let line = format!(
+            "  {phase:<6} {:>7}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}  {comparison}",
+            stats.samples, millis(stats.min), millis(stats.median), millis(stats.mean), millis(stats.stddev), stats.outliers,
+        );
+        lines.push(line.trim_end().to_string());
+    }
+    (lines.join("\n"), regressions)
+}
@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use crate::{AocError, Part, bench::{Budget, DEFAULT_BASELINE}};
+
+pub const USAGE : &str = "\
+Usage: aoc-2025 <command> [options]
+
+Commands:
+    run <day> [--part 1|2|both] [--input PATH] [--timings] [--record]
+                        Solve one day (both parts by default), reading inputs/day<day>.txt
+                        unless PATH is given. --timings shows how long reading, parsing and
+                        solving took. `aoc-2025 <day> ...` is short for this.
+    all [--parallel] [--json] [--record]
+                        Solve every day, printing a table of answers and timings, or JSON.
+                        --parallel spreads the days over all cores.
+    list                List the registered days and whether their inputs are present
+    bench [<day>] [--iterations N | --time MS] [--warmup N] [--baseline PATH]
+          [--save-baseline] [--threshold PERCENT]
+                        Time each phase of one or every day for MS milliseconds (1000 by
+                        default) or N iterations, after N warmup iterations (1 by default).
+                        Medians are compared against the baseline file (target/bench/baseline.txt
+                        by default), flagging regressions beyond PERCENT (10 by default);
+                        --save-baseline records this run as the new baseline.
+    test [<day>]        Solve one or every day on its example input, inputs/day<day>_test.txt
+    fetch [<day>]       Download one or every day's missing input from the puzzle server. Set
+                        the session token with AOC_SESSION or `session` in aoc.toml, and the
+                        server with AOC_BASE_URL or `base_url` (adventofcode.com by default).
+                        `run` and `all` fetch missing inputs too once there's a token.
+    submit <day> <part> [--force]
+                        Solve one part and post the answer to the puzzle server, recording the
+                        attempt in inputs/attempts.txt. Answers already rejected, or contradicting
+                        an earlier too high or too low, aren't sent unless --force is given.
+    grammar <day> [--part 1|2] [--svg]
+                        Print the grammar of one part's input (part 1 by default) as EBNF, or
+                        with --svg as railroad diagrams
+    new <day>           Create src/day<day>.rs from the day 0 template, register it in src/lib.rs,
+                        and create empty inputs/day<day>.txt and inputs/day<day>_test.txt
+
+Answers are checked against inputs/answers.toml, and --record saves them there as the accepted
+ones. Wrong answers make the command fail.
+
+Options:
+    -h, --help          Print this message";
+
+const DEFAULT_BUDGET : Budget = Budget::Time(Duration::from_secs(1));
+const DEFAULT_WARMUP : usize = 1;
+const DEFAULT_THRESHOLD : f64 = 10.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Run { day : i32, parts : Vec<Part>, input : Option<String>, timings : bool, record : bool },
+    All { parallel : bool, json : bool, record : bool },
+    List,
+    Bench { day : Option<i32>, options : BenchOptions },
+    Test { day : Option<i32> },
+    Fetch { day : Option<i32> },
+    Submit { day : i32, part : Part, force : bool },
+    Grammar { day : i32, part : Part, svg : bool },
+    New { day : i32 },
+    Help,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchOptions {
+    pub budget : Budget,
+    pub warmup : usize,
+    pub baseline : String,
+    pub save_baseline : bool,
+    // As a fraction, e.g. 0.1 for 10%.
+    pub threshold : f64,
+}
+
+fn usage(message : impl Into<String>) -> AocError {
+    AocError::Usage(message.into())
+}
+
+struct Args {
+    args : Vec<String>,
+    next : usize,
+}
+
+impl Args {
+    fn next(&mut self) -> Option<String> {
+        let arg = self.args.get(self.next).cloned();
+        self.next += 1;
+        arg
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.args.get(self.next).map(String::as_str)
+    }
+
+    // The value after `flag`.
+    fn value(&mut self, flag : &str) -> Result<String, AocError> {
+        self.next().ok_or_else(|| usage(format!("{flag} needs a value")))
+    }
+
+    // An optional positional day number, which comes before any flags.
+    fn day(&mut self) -> Result<Option<i32>, AocError> {
+        match self.peek() {
+            Some(arg) if !arg.starts_with('-') => {
+                let arg = self.next().expect("peeked");
+                parse_day(&arg).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn required_day(&mut self, command : &str) -> Result<i32, AocError> {
+        self.day()?.ok_or_else(|| usage(format!("`{command}` needs a day number")))
+    }
+}
+
+fn parse_day(arg : &str) -> Result<i32, AocError> {
+    match arg.parse() {
+        Ok(day @ 0..=25) => Ok(day),
+        _ => Err(usage(format!("`{arg}` isn't a day number (0 to 25)"))),
+    }
+}
+
+// Day 0 is the template, which the puzzle server knows nothing about.
+fn puzzle_day(day : i32, command : &str) -> Result<i32, AocError> {
+    match day {
+        0 => Err(usage(format!("day 0 is the template, which `{command}` can't be used on"))),
+        day => Ok(day),
+    }
+}
+
+fn parse_parts(arg : &str) -> Result<Vec<Part>, AocError> {
+    match arg {
+        "1" => Ok(vec![Part::Easy]),
+        "2" => Ok(vec![Part::Hard]),
+        "both" => Ok(Part::BOTH.to_vec()),
+        _ => Err(usage(format!("`--part` must be 1, 2 or both, not `{arg}`"))),
+    }
+}
+
+// Parses the arguments after the program name.
+pub fn parse_args(args : impl IntoIterator<Item = String>) -> Result<Command, AocError> {
+    let mut args = Args { args: args.into_iter().collect(), next: 0 };
+    if args.args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Command::Help);
+    }
+    let Some(command) = args.next() else {
+        return Err(usage("missing command"));
+    };
+
+    let command = match command.as_str() {
+        "run" => parse_run(&mut args)?,
+        day if day.starts_with(|c : char| c.is_ascii_digit()) => {
+            args.next -= 1;
+            parse_run(&mut args)?
+        }
+        "all" => {
+            let (mut parallel, mut json, mut record) = (false, false, false);
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--parallel" => parallel = true,
+                    "--json" => json = true,
+                    "--record" => record = true,
+                    _ => return Err(usage(format!("unknown option `{flag}` for `all`"))),
+                }
+            }
+            Command::All { parallel, json, record }
+        }
+        "list" => Command::List,
+        "bench" => parse_bench(&mut args)?,
+        "test" => Command::Test { day: args.day()? },
+        "fetch" => Command::Fetch { day: args.day()?.map(|day| puzzle_day(day, "fetch")).transpose()? },
+        "submit" => parse_submit(&mut args)?,
+        "grammar" => parse_grammar(&mut args)?,
+        "new" => Command::New { day: args.required_day("new")? },
+        "help" => Command::Help,
+        _ => return Err(usage(format!("unknown command `{command}`"))),
+    };
+    match args.next() {
+        Some(extra) => Err(usage(format!("unexpected argument `{extra}`"))),
+        None => Ok(command),
+    }
+}
+
+// A number for `flag` that's at least `min`.
+fn number<N : std::str::FromStr + PartialOrd + std::fmt::Display>(args : &mut Args, flag : &str, min : N) -> Result<N, AocError> {
+    let value = args.value(flag)?;
+    match value.parse() {
+        Ok(n) if n >= min => Ok(n),
+        Ok(_) => Err(usage(format!("`{flag}` must be at least {min}"))),
+        Err(_) => Err(usage(format!("`{flag}` must be a number, not `{value}`"))),
+    }
+}
+
+fn parse_bench(args : &mut Args) -> Result<Command, AocError> {
+    let day = args.day()?;
+    let mut options = BenchOptions {
+        budget: DEFAULT_BUDGET,
+        warmup: DEFAULT_WARMUP,
+        baseline: DEFAULT_BASELINE.to_string(),
+        save_baseline: false,
+        threshold: DEFAULT_THRESHOLD / 100.0,
+    };
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--iterations" => options.budget = Budget::Iterations(number(args, &flag, 1)?),
+            "--time" => options.budget = Budget::Time(Duration::from_millis(number(args, &flag, 1)?)),
+            "--warmup" => options.warmup = number(args, &flag, 0)?,
+            "--baseline" => options.baseline = args.value(&flag)?,
+            "--save-baseline" => options.save_baseline = true,
+            "--threshold" => options.threshold = number(args, &flag, 0.0)? / 100.0,
+            _ => return Err(usage(format!("unknown option `{flag}` for `bench`"))),
+        }
+    }
+    Ok(Command::Bench { day, options })
+}
+
+fn parse_submit(args : &mut Args) -> Result<Command, AocError> {
+    let day = puzzle_day(args.required_day("submit")?, "submit")?;
+    let part = match args.next().as_deref() {
+        Some("1") => Part::Easy,
+        Some("2") => Part::Hard,
+        Some(arg) => return Err(usage(format!("the part must be 1 or 2, not `{arg}`"))),
+        None => return Err(usage("`submit` needs a part, 1 or 2")),
+    };
+    let mut force = false;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--force" => force = true,
+            _ => return Err(usage(format!("unknown option `{flag}` for `submit`"))),
+        }
+    }
+    Ok(Command::Submit { day, part, force })
+}
+
+fn parse_grammar(args : &mut Args) -> Result<Command, AocError> {
+    let day = args.required_day("grammar")?;
+    let mut part = Part::Easy;
+    let mut svg = false;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--part" => match parse_parts(&args.value(&flag)?)?.as_slice() {
+                [one] => part = *one,
+                _ => return Err(usage("`--part` must be 1 or 2 for `grammar`")),
+            },
+            "--svg" => svg = true,
+            _ => return Err(usage(format!("unknown option `{flag}` for `grammar`"))),
+        }
+    }
+    Ok(Command::Grammar { day, part, svg })
+}
+
+fn parse_run(args : &mut Args) -> Result<Command, AocError> {
+    let day = args.required_day("run")?;
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+    let (mut timings, mut record) = (false, false);
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--part" => parts = parse_parts(&args.value(&flag)?)?,
+            "--input" => input = Some(args.value(&flag)?),
+            "--timings" => timings = true,
+            "--record" => record = true,
+            _ => return Err(usage(format!("unknown option `{flag}` for `run`"))),
+        }
+    }
+    Ok(Command::Run { day, parts, input, timings, record })
+}
@@ -1,25 +1,32 @@
-use crate::{Day, parser_utils::{chr, nat, whitespace}};
+use crate::{Day, parser_utils::from_parse};
 use parser_combinators::Parser;
 
 pub struct Day0;
 
 impl Day for Day0 {
-    type Input = Vec<char>;
+    type Input = Vec<u64>;
+    // Each part can answer with any type that implements `Display`.
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         0
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        todo!()
+        from_parse()
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
-        println!("{input:?}");
-        todo!()
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
+        Ok(input.into_iter().sum())
     }
 
-    // fn solve_hard(input : Self::Input) -> u64 {
-    //     todo!()
-    // }
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
+        Ok(input.len() as u64)
+    }
+}
+
+crate::examples! {
+    Day0;
+    example: file "inputs/day0_test.txt" => easy 6, hard 3;
 }
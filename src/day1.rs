@@ -1,37 +1,45 @@
-use crate::{Day, parser_utils::{chr, nat, whitespace}};
-use parser_combinators::Parser;
+use crate::{Day, parser_utils::whitespace};
+use parser_combinators::{FromParse, Parse, Parser, Syntax};
 
 pub struct Day1;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Parse)]
 enum Direction {
+    #[parse(prefix = "L")]
     Left,
+    #[parse(prefix = "R")]
     Right,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Parse)]
 pub struct Rotation {
     dir : Direction,
-    amount : i32,
+    amount : u32,
+}
+
+pub fn rotations<'a>() -> impl Syntax<'a, char, O=Vec<Rotation>> {
+    Rotation::parser().sep_end_by1(whitespace().print_as("\n".chars()))
 }
 
 impl Day for Day1 {
     type Input = Vec<Rotation>;
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         1
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        (chr('L').map_const(Direction::Left).disj(chr('R').map_const(Direction::Right)),
-         nat()).map(|(dir, amount)| Rotation { dir, amount }).sep_end_by1(whitespace())
+        rotations()
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
         // println!("{:?}", input);
         let mut dial = 50;
         let mut password = 0;
         for Rotation { dir, amount } in input {
+            let amount = i64::from(amount);
             match dir {
                 Direction::Left => dial -= amount,
                 Direction::Right => dial += amount,
@@ -41,17 +49,18 @@ impl Day for Day1 {
                 password += 1;
             }
         }
-        password
+        Ok(password)
     }
 
-    fn solve_hard(input : Self::Input) -> u64 {
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
         let mut dial = 50;
         let mut password = 0;
         for Rotation { dir, amount } in input {
             if amount == 0 {
                 continue;
             }
-            
+            let amount = i64::from(amount);
+
             let mut new_dial = match dir {
                 Direction::Left => dial - amount,
                 Direction::Right => dial + amount,
@@ -73,6 +82,11 @@ impl Day for Day1 {
 
             dial = new_dial;
         }
-        password
+        Ok(password)
     }
 }
+
+crate::examples! {
+    Day1;
+    example: "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n" => easy 3, hard 6;
+}
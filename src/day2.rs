@@ -1,23 +1,37 @@
-use crate::{Day, parser_utils::{chr, nat, whitespace}};
-use parser_combinators::Parser;
+use crate::{Day, parser_utils::{keyword, whitespace}, range::Range};
+use parser_combinators::{FromParse, Parser, Syntax};
 
 pub struct Day2;
 
+pub fn ranges<'a>() -> impl Syntax<'a, char, O=Vec<Range>> {
+    Range::parser().sep_by1(keyword(",")).seql(whitespace().print_as("\n".chars()))
+}
+
 impl Day for Day2 {
-    type Input = Vec<(u64, u64)>;
+    type Input = Vec<Range>;
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         2
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        (nat().seql(chr('-')), nat()).sep_by1(chr(',')).seql(whitespace())
+        ranges()
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
+    // The solvers count each ID's digits, which 0 doesn't have
+    fn validate(input : &Self::Input) -> Result<(), String> {
+        match input.iter().find(|Range(lo, _)| *lo == 0) {
+            Some(Range(lo, hi)) => Err(format!("range {lo}-{hi} includes 0, but IDs are positive")),
+            None => Ok(()),
+        }
+    }
+
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
         let mut total = 0;
-        for (start, end) in input {
+        for Range(start, end) in input {
             for num in start..=end {
                 let digits = num.ilog10() + 1;
                 if digits % 2 == 1 {
@@ -29,13 +43,13 @@ impl Day for Day2 {
                 }
             }
         }
-        total
+        Ok(total)
     }
 
-    fn solve_hard(input : Self::Input) -> u64 {
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
         let mut total = 0;
-        for (start, end) in input {
+        for Range(start, end) in input {
             for num in start..=end {
                 let digits = num.ilog10() + 1;
                 let mut valid = true;
@@ -67,6 +81,11 @@ impl Day for Day2 {
                 }
             }
         }
-        total
+        Ok(total)
     }
 }
+
+crate::examples! {
+    Day2;
+    example: file "inputs/day2_test.txt" => easy 1227775554, hard 4174379265;
+}
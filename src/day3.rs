@@ -5,6 +5,8 @@ pub struct Day3;
 
 impl Day for Day3 {
     type Input = Vec<Vec<u8>>;
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         3
@@ -14,13 +16,16 @@ impl Day for Day3 {
         digit().map(|d| d.to_digit(10).unwrap() as u8).many1().sep_end_by1(chr('\n'))
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
         let mut total : u64 = 0;
-        for line in input {
+        for (n, line) in input.into_iter().enumerate() {
+            let len = line.len();
+            if len < 2 {
+                return Err(format!("bank {} has {len} batteries, but 2 are needed", n + 1));
+            }
             let mut most = 0;
             let mut second_most = 0;
-            let len = line.len();
             for (i, jolt) in line.into_iter().enumerate() {
                 if jolt > most && i != len - 1{
                     most = jolt;
@@ -32,22 +37,23 @@ impl Day for Day3 {
             // println!("{most}, {second_most}");
             total += (most * 10 + second_most) as u64;
         }
-        total
+        Ok(total)
     }
 
-    fn solve_hard(input : Self::Input) -> u64 {
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
         let mut total : u64 = 0;
-        for line in input {
-            let mut mosts = [0; 12];
+        for (n, line) in input.into_iter().enumerate() {
             let len = line.len();
+            if len < 12 {
+                return Err(format!("bank {} has {len} batteries, but 12 are needed", n + 1));
+            }
+            let mut mosts = [0; 12];
             for (i, jolt) in line.into_iter().enumerate() {
                 for j in 0..12 {
-                    if jolt > mosts[j] && i <= len - 12 + j {
+                    if jolt > mosts[j] && i + 12 <= len + j {
                         mosts[j] = jolt;
-                        for k in j+1..12 {
-                            mosts[k] = 0;
-                        }
+                        mosts[j+1..].fill(0);
                         break
                     }
                 }
@@ -60,6 +66,11 @@ impl Day for Day3 {
             }
             total += val;
         }
-        total
+        Ok(total)
     }
 }
+
+crate::examples! {
+    Day3;
+    example: file "inputs/day3_test.txt" => easy 357, hard 3121910778619;
+}
@@ -1,29 +1,37 @@
-use crate::{Day, parser_utils::chr, grid::Grid};
-use parser_combinators::Parser;
+use crate::{Day, parser_utils::keyword, grid::Grid};
+use parser_combinators::{Parser, Syntax};
 
 pub struct Day4;
 
+pub fn paper_grid<'a>() -> impl Syntax<'a, char, O=Grid<bool>> {
+    keyword(".").map_const(false).disj(keyword("@").map_const(true)).many1().sep_end_by1(keyword("\n"))
+        .satisfy(|rows| rows.iter().all(|row| row.len() == rows[0].len()))
+        .iso(Grid::new, |grid| Some(grid.rows()))
+}
+
 impl Day for Day4 {
     type Input = Grid<bool>;
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         4
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        chr('.').map_const(false).disj(chr('@').map_const(true)).many1().sep_end_by1(chr('\n')).map(Grid::new)
+        paper_grid()
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
-        input.index_iter().filter(|p| input[*p] && input.neighbors8(*p).filter(|x| **x).count() < 4).count() as u64
+        Ok(input.index_iter().filter(|p| input[*p] && input.neighbors8(*p).filter(|x| **x).count() < 4).count() as u64)
     }
 
-    fn solve_hard(mut input : Self::Input) -> u64 {
+    fn solve_hard(mut input : Self::Input) -> Result<u64, String> {
         let mut total = 0;
         loop {
             let accessible = input.index_iter().filter(|p| input[*p] && input.neighbors8(*p).filter(|x| **x).count() < 4).collect::<Vec<_>>();
-            if accessible.len() == 0 {
+            if accessible.is_empty() {
                 break
             }
             for p in accessible {
@@ -31,6 +39,11 @@ impl Day for Day4 {
                 input[p] = false;
             }
         }
-        total
+        Ok(total)
     }
 }
+
+crate::examples! {
+    Day4;
+    example: file "inputs/day4_test.txt" => easy 13, hard 43;
+}
@@ -1,30 +1,43 @@
 use std::iter::once;
 
-use crate::{Day, parser_utils::{chr, nat}};
-use parser_combinators::Parser;
+use crate::{Day, range::Range};
+use parser_combinators::{FromParse, Parser, Syntax};
 
 pub struct Day5;
 
+pub fn inventory<'a>() -> impl Syntax<'a, char, O=(Vec<Range>, Vec<u64>)> {
+    <(Vec<Range>, Vec<u64>)>::parser()
+}
+
 impl Day for Day5 {
-    type Input = (Vec<(u64, u64)>, Vec<u64>);
+    type Input = (Vec<Range>, Vec<u64>);
+    type Easy = u64;
+    type Hard = u64;
 
     fn day_number() -> i32 {
         5
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        ((nat().seql(chr('-')), nat()).sep_end_by1(chr('\n')),
-         chr('\n').seqr(nat().sep_end_by1(chr('\n'))))
+        inventory()
     }
 
-    fn solve_easy(input : Self::Input) -> u64 {
+    // Merging sums `hi - lo + 1` over the ranges, so none may be backwards
+    fn validate(input : &Self::Input) -> Result<(), String> {
+        match input.0.iter().find(|Range(lo, hi)| lo > hi) {
+            Some(Range(lo, hi)) => Err(format!("range {lo}-{hi} is backwards")),
+            None => Ok(()),
+        }
+    }
+
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
         // println!("{input:?}");
         let fresh_ranges = &input.0;
         let ids = &input.1;
 
         let mut count = 0;
         for id in ids {
-            for (lo, hi) in fresh_ranges {
+            for Range(lo, hi) in fresh_ranges {
                 if id >= lo && id <= hi {
                     count += 1;
                     break
@@ -32,14 +45,15 @@ impl Day for Day5 {
             }
         }
 
-        count
+        Ok(count)
     }
 
-    fn solve_hard(input : Self::Input) -> u64 {
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
         let fresh_ranges = input.0;
         let mut merged_ranges : Vec<(u64, u64)> = Vec::new();
 
-        for new_range @ (new_lo, new_hi) in fresh_ranges {
+        for Range(new_lo, new_hi) in fresh_ranges {
+            let new_range = (new_lo, new_hi);
             let overlapping = merged_ranges.iter().cloned().filter(|(lo, hi)| *hi >= new_lo && new_hi >= *lo);
             let not_overlapping = merged_ranges.iter().cloned().filter(|(lo, hi)| !(*hi >= new_lo && new_hi >= *lo));
 
@@ -49,6 +63,12 @@ impl Day for Day5 {
         }
         // println!("{merged_ranges:?}");
         
-        merged_ranges.into_iter().map(|(lo, hi)| hi - lo + 1).sum()
+        Ok(merged_ranges.into_iter().map(|(lo, hi)| hi - lo + 1).sum())
     }
 }
+
+crate::examples! {
+    Day5;
+    example: file "inputs/day5_test.txt" => easy 3, hard 14;
+    nested_ranges: "1-10\n2-3\n\n5\n11\n" => easy 1, hard 10;
+}
@@ -1,5 +1,3 @@
-use std::ops::Add;
-
 use crate::{Day, parser_utils::{chr, nat, whitespace}};
 use parser_combinators::Parser;
 
@@ -13,19 +11,35 @@ pub enum Op {
 
 impl Day for Day6 {
     type Input = ([Vec<u64>; 4], Vec<Op>);
+    type Easy = u128;
+    type Hard = u128;
 
     fn day_number() -> i32 {
         6
     }
     
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        (whitespace().seqr(nat()).sep_by1(chr(' ').many1()).sep_end_by1(chr('\n')).map(|v| v.try_into().unwrap()),
+        (whitespace().seqr(nat()).sep_by1(chr(' ').many1()).sep_end_by1(chr('\n'))
+            .satisfy(|rows| rows.len() == 4).map(|v| v.try_into().expect("four rows")),
          chr('+').map_const(Op::Add).disj(chr('*').map_const(Op::Mult)).sep_by1(chr(' ').many1())).seql(whitespace())
     }
 
+    // The solvers zip the columns together, which would silently drop any that don't line up, and
+    // strip trailing 0s, which would never finish for a 0
+    fn validate(input : &Self::Input) -> Result<(), String> {
+        let ops = input.1.len();
+        if let Some(i) = input.0.iter().position(|row| row.len() != ops) {
+            return Err(format!("row {} has {} numbers but there are {ops} operators", i + 1, input.0[i].len()));
+        }
+        match input.0.iter().position(|row| row.contains(&0)) {
+            Some(i) => Err(format!("row {} contains a 0", i + 1)),
+            None => Ok(()),
+        }
+    }
+
     // Takes advantage of the fact that the input data doesn't already contain any 0s to accept the modified input file
     // Also works on the unmodified input file
-    fn solve_easy(input : Self::Input) -> u64 {
+    fn solve_easy(input : Self::Input) -> Result<u128, String> {
         // println!("{input:?}");
         let mut total = 0;
         for ((((n0, n1), n2), n3), op) in input.0[0].iter().zip(input.0[1].iter()).zip(input.0[2].iter()).zip(input.0[3].iter()).zip(input.1.iter()) {
@@ -45,20 +59,22 @@ impl Day for Day6 {
             while n3 % 10 == 0 {
                 n3 /= 10;
             }
+            // Four numbers can multiply to more than a u64 holds
+            let (n0, n1, n2, n3) = (n0 as u128, n1 as u128, n2 as u128, n3 as u128);
             total += match op {
                 Op::Add => n0 + n1 + n2 + n3,
                 Op::Mult => n0 * n1 * n2 * n3,
             };
         }
-        total
+        Ok(total)
     }
 
     // Requires using a modified input file that replaces spaces inside of number inputs with 0s
     // Takes advantage of the fact that the input data doesn't already contain any 0s
-    fn solve_hard(input : Self::Input) -> u64 {
+    fn solve_hard(input : Self::Input) -> Result<u128, String> {
         let mut total = 0;
         for ((((n0, n1), n2), n3), op) in input.0[0].iter().zip(input.0[1].iter()).zip(input.0[2].iter()).zip(input.0[3].iter()).zip(input.1.iter()) {
-            let mut solution = match op { Op::Add => 0, Op::Mult => 1 };
+            let mut solution : u128 = match op { Op::Add => 0, Op::Mult => 1 };
             
             let digits = n0.max(n1).max(n2).max(n3).ilog10() + 1;
 
@@ -68,18 +84,24 @@ impl Day for Day6 {
                 let d2 = n2 / (10_u64.pow(i)) % 10;
                 let d3 = n3 / (10_u64.pow(i)) % 10;
                 let mut num = d0 * 1000 + d1 * 100 + d2 * 10 + d3;
-                while num % 10 == 0 {
+                while num != 0 && num % 10 == 0 {
                     num /= 10;
                 }
                 // println!("{num}");
                 match op {
-                    Op::Add => solution += num,
-                    Op::Mult => solution *= num,
+                    Op::Add => solution += num as u128,
+                    Op::Mult => solution *= num as u128,
                 }
             }
 
             total += solution;
         }
-        total
+        Ok(total)
     }
 }
+
+// The spaces that align the numbers are already replaced with 0s, as the hard part requires.
+crate::examples! {
+    Day6;
+    aligned_both_ways: "006 41\n012 52\n078 30\n345 60\n+   *\n" => easy 38817, hard 60894;
+}
@@ -0,0 +1,95 @@
+use std::{error::Error, fmt, io};
+
+use parser_combinators::ParseError;
+
+#[derive(Debug)]
+pub enum AocError {
+    // The command line doesn't make sense.
+    Usage(String),
+    // A file couldn't be read or written.
+    Io { path : String, source : io::Error },
+    // The input doesn't match the day's grammar. `context` is the offending line with a caret under `column`.
+    Parse { path : String, line : usize, column : usize, message : String, context : String },
+    // The input parsed, but breaks an assumption the solver relies on.
+    Validation(String),
+    // The solver couldn't answer for the input, or panicked, which is a bug.
+    Solver { message : String, panicked : bool },
+    // `bench` found phases slower than the baseline.
+    Regression(String),
+    // An answer differs from the accepted one in the answers file.
+    WrongAnswer(String),
+    // The puzzle server couldn't be reached, or answered with an error.
+    Http(String),
+}
+
+impl AocError {
+    // Locates `err` in `input`, with 1-based line and column numbers.
+    pub fn parse(path : &str, input : &[char], err : ParseError<char>) -> Self {
+        let offset = input.len() - err.rest.len();
+        let line_start = input[..offset].iter().rposition(|c| *c == '\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].iter().position(|c| *c == '\n').map_or(input.len(), |i| offset + i);
+        let line = input[..offset].iter().filter(|c| **c == '\n').count() + 1;
+        let column = offset - line_start + 1;
+        let text : String = input[line_start..line_end].iter().collect();
+        let context = format!("{text}\n{}^", " ".repeat(column - 1));
+        AocError::Parse { path: path.to_string(), line, column, message: err.typ.to_string(), context }
+    }
+
+    // A short name for this kind of error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AocError::Usage(_) => "usage",
+            AocError::Io { .. } => "io",
+            AocError::Parse { .. } => "parse",
+            AocError::Validation(_) => "validation",
+            AocError::Solver { .. } => "solver",
+            AocError::Regression(_) => "regression",
+            AocError::WrongAnswer(_) => "wrong",
+            AocError::Http(_) => "http",
+        }
+    }
+
+    // The process exit code for this kind of error, so that scripts can tell them apart.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::Usage(_) => 1,
+            AocError::Io { .. } => 2,
+            AocError::Parse { .. } => 3,
+            AocError::Validation(_) => 4,
+            AocError::Solver { .. } => 5,
+            AocError::Regression(_) => 6,
+            AocError::WrongAnswer(_) => 7,
+            AocError::Http(_) => 8,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Usage(message) => write!(f, "{message}"),
+            AocError::Io { path, source } => write!(f, "{path}: {source}"),
+            AocError::Parse { path, line, column, message, context } => {
+                write!(f, "{path}:{line}:{column}: {message}")?;
+                for context_line in context.lines() {
+                    write!(f, "\n    {context_line}")?;
+                }
+                Ok(())
+            }
+            AocError::Validation(message) => write!(f, "invalid input: {message}"),
+            AocError::Solver { message, panicked: false } => write!(f, "solver failed: {message}"),
+            AocError::Solver { message, panicked: true } => write!(f, "solver panicked: {message}"),
+            AocError::Regression(message) | AocError::WrongAnswer(message) => write!(f, "{message}"),
+            AocError::Http(message) => write!(f, "puzzle server: {message}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
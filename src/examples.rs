@@ -0,0 +1,73 @@
+use crate::{Day, Part, read_input};
+
+// Example inputs from the puzzle descriptions, with their answers. Each day declares its examples
+// with `examples!`, which generates a `#[test]` for each:
+//
+//     examples! {
+//         Day5;
+//         from_file: file "inputs/day5_test.txt" => easy 3, hard 14;
+//         one_range: "3-5\n\n4\n" => easy 1;
+//     }
+//
+// Examples are either files, relative to the crate root, or inline strings. Answers are numbers or
+// strings, and parts without an answer are skipped. Answers that aren't `u64`s, such as negative
+// numbers, are compared as they print, so they're written as strings: `easy "-3"`.
+#[macro_export]
+macro_rules! examples {
+    (@input file $path:literal) => { $crate::examples::Example::File($path) };
+    (@input $text:literal) => { $crate::examples::Example::Inline($text) };
+    (@part easy) => { $crate::Part::Easy };
+    (@part hard) => { $crate::Part::Hard };
+    ($day:ident; $($name:ident : $($kind:ident)? $input:literal => $($part:ident $answer:expr),+);+ $(;)?) => {
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $name() {
+                    $crate::examples::check::<super::$day>(
+                        $crate::examples!(@input $($kind)? $input),
+                        &[$(($crate::examples!(@part $part), $crate::examples::Expected::text(&$answer))),+],
+                    );
+                }
+            )+
+        }
+    };
+}
+
+// Implemented for `u64` alone among the integers so that answer literals don't default to `i32`.
+pub trait Expected {
+    fn text(&self) -> String;
+}
+
+impl Expected for u64 {
+    fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Expected for &str {
+    fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+pub enum Example {
+    File(&'static str),
+    Inline(&'static str),
+}
+
+// Solves `example` with `D`, panicking unless each part's answer is as `expected`.
+pub fn check<D : Day>(example : Example, expected : &[(Part, String)]) {
+    let (path, text) = match example {
+        Example::File(path) => (path, read_input(path).unwrap_or_else(|e| panic!("{e}"))),
+        Example::Inline(text) => ("inline example", text.to_string()),
+    };
+    let raw_chars : Vec<char> = text.chars().collect();
+    let parts : Vec<Part> = expected.iter().map(|(part, _)| *part).collect();
+    for ((part, result), (_, answer)) in D::solve_parts(path, &raw_chars, &parts).into_iter().zip(expected) {
+        match result {
+            Ok(solution) => assert_eq!(solution.answer, *answer, "day {} {part} on {path}", D::day_number()),
+            Err(e) => panic!("day {} {part} on {path}: {e}", D::day_number()),
+        }
+    }
+}
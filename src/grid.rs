@@ -1,8 +1,10 @@
 use std::ops::{Index, IndexMut};
 
+use parser_combinators::{FromParse, Parser, Syntax, literal};
+
 type Point = (i64, i64);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grid<T> {
     items: Vec<T>,
     width: i64,
@@ -11,7 +13,7 @@ pub struct Grid<T> {
 
 impl<T> Grid<T> {
     pub fn new(grid : Vec<Vec<T>>) -> Self {
-        let width = grid[0].len();
+        let width = grid.first().map_or(0, Vec::len);
         let height = grid.len();
         Grid {
             items: grid.into_iter().flatten().collect(),
@@ -20,6 +22,10 @@ impl<T> Grid<T> {
         }
     }
 
+    pub fn rows(&self) -> Vec<Vec<T>> where T : Clone {
+        self.items.chunks(self.width.max(1) as usize).map(|row| row.to_vec()).collect()
+    }
+
     pub fn width(&self) -> i64 {
         self.width
     }
@@ -58,6 +64,16 @@ impl<T> Grid<T> {
     }
 }
 
+// One row per line, with cells written back to back, so `T` should be a single character such as
+// `char` or a derived enum.
+impl<T : FromParse + Clone> FromParse for Grid<T> {
+    fn parser<'a>() -> impl Syntax<'a, char, O=Self> {
+        T::parser().many1().sep_by1(literal("\n"))
+            .satisfy(|rows| rows.iter().all(|row| row.len() == rows[0].len()))
+            .iso(Grid::new, |grid| Some(grid.rows()))
+    }
+}
+
 pub fn add_points(point1: Point, point2: Point) -> Point {
     (point1.0 + point2.0, point1.1 + point2.1)
 }
@@ -1,44 +1,255 @@
-use std::{fs::File, io::Read};
+use std::{cell::Cell, fmt, fs::read_to_string, panic::{self, AssertUnwindSafe, catch_unwind}, sync::Once, time::{Duration, Instant}};
 
-use parser_combinators::Parser;
-use parser_utils::parse_all;
+use parser_combinators::{ParseResult, Parser, fuzz::panic_message};
+use parser_utils::try_parse_all;
 
+pub use answers::Answers;
+pub use error::AocError;
+pub use registry::{DynDay, Registry};
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
 pub mod parser_utils;
 pub mod grid;
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
+pub mod range;
+pub mod registry;
+pub mod scaffold;
+pub mod submit;
+pub mod summary;
+pub mod web;
+
+// Declares each day's module and registers its `Day`, so adding a day is one line here.
+macro_rules! days {
+    ($($module:ident::$day:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        // Each registered day with the name of its module, which should be `day` and its number.
+        pub fn day_modules() -> Vec<(&'static str, &'static dyn DynDay)> {
+            vec![$((stringify!($module), &$module::$day as &dyn DynDay)),*]
+        }
+
+        pub fn registry() -> Registry {
+            Registry::new(day_modules().into_iter().map(|(_, day)| day).collect())
+        }
+    };
+}
+
+days!(
+    day0::Day0,
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Easy,
+    Hard,
+}
+
+impl Part {
+    pub const BOTH : [Part; 2] = [Part::Easy, Part::Hard];
+
+    // The puzzle's own numbering, 1 or 2.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::Easy => 1,
+            Part::Hard => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::Easy => write!(f, "Easy"),
+            Part::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+pub fn default_input(day_number : i32) -> String {
+    format!("inputs/day{day_number}.txt")
+}
+
+// One part's answer, with how long parsing and solving took. Days can answer with any type that
+// prints, so the answer is kept as it prints.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub answer : String,
+    pub parse_time : Duration,
+    pub solve_time : Duration,
+    // Whether this part solved a clone of another part's parse, in which case `parse_time` is the
+    // time taken to clone it.
+    pub reused_parse : bool,
+}
+
+// How long it took to read an input file and convert it to chars.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InputTimes {
+    pub read : Duration,
+    pub chars : Duration,
+}
+
+pub fn millis(time : Duration) -> String {
+    format!("{:.3}", time.as_secs_f64() * 1000.0)
+}
 
 pub trait Day {
-    type Input;
+    type Input : Clone;
+    // The types of the answers, which may differ between the parts: a count, a negative number, a
+    // `u128` that doesn't fit in a `u64`, or a string such as a letter code.
+    type Easy : fmt::Display;
+    type Hard : fmt::Display;
 
     fn day_number() -> i32;
     
+    // Days whose input type implements `FromParse` can use `parser_utils::from_parse()`.
     fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input>;
-    fn parse_hard<'a>() -> impl Parser<'a, char, O=Self::Input> {
-        Self::parse_easy()
+    // Days whose hard part reads the input differently return its parser here. Otherwise both
+    // parts share one parse of the input.
+    fn parse_hard<'a>() -> Option<impl Parser<'a, char, O=Self::Input>> {
+        None::<fn(&'a [char]) -> ParseResult<'a, char, Self::Input>>
     }
-    fn solve_easy(input : Self::Input) -> u64;
-    fn solve_hard(input : Self::Input) -> u64 {
-        Self::solve_easy(input)
+    // Checks assumptions about the input that the grammar doesn't express.
+    fn validate(_input : &Self::Input) -> Result<(), String> {
+        Ok(())
     }
 
-    fn run(filename : Option<String>, include_hard : bool) {
-        let filename = filename.unwrap_or(format!("inputs/day{}.txt", Self::day_number()));
-        let mut raw = String::new();
-        File::open(filename).expect("input file exists").read_to_string(&mut raw).expect("input file readable");
-        let raw_chars : Vec<char> = raw.chars().collect();
+    // Inputs a solver can't answer for, even though they parsed and validated, are an `Err` with
+    // the reason. Panics are caught too, but are bugs.
+    fn solve_easy(input : Self::Input) -> Result<Self::Easy, String>;
+    fn solve_hard(input : Self::Input) -> Result<Self::Hard, String>;
+
+    // Parses and solves one part of `input`, which was read from `path`.
+    fn solve_part(part : Part, path : &str, input : &[char]) -> Result<Solution, AocError> {
+        match part {
+            Part::Easy => timed_solve::<Self, _>(Self::parse_easy(), Self::solve_easy, path, input),
+            Part::Hard => match Self::parse_hard() {
+                Some(parser) => timed_solve::<Self, _>(parser, Self::solve_hard, path, input),
+                None => timed_solve::<Self, _>(Self::parse_easy(), Self::solve_hard, path, input),
+            },
+        }
+    }
 
-        let easy_parsed = parse_all(Self::parse_easy(), &raw_chars);
-        println!("Easy: {}", Self::solve_easy(easy_parsed));
+    // Parses and solves `parts` of `input`, which was read from `path`. Unless the hard part has
+    // its own parser, the input is only parsed once and later parts solve a clone of it.
+    fn solve_parts(path : &str, input : &[char], parts : &[Part]) -> Vec<(Part, Result<Solution, AocError>)> {
+        let mut shared : Option<(Self::Input, Duration)> = None;
+        let mut results = Vec::new();
+        for (i, &part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            let parsed = match shared.take() {
+                Some((parsed, clone_time)) => Ok((parsed, clone_time, true)),
+                None => {
+                    let parsed = match (part, Self::parse_hard()) {
+                        (Part::Hard, Some(parser)) => parse_input::<Self>(parser, path, input),
+                        _ => parse_input::<Self>(Self::parse_easy(), path, input),
+                    };
+                    parsed.map(|parsed| (parsed, start.elapsed(), false))
+                }
+            };
+            let result = parsed.and_then(|(parsed, parse_time, reused_parse)| {
+                if Self::parse_hard().is_none() && i + 1 < parts.len() {
+                    let start = Instant::now();
+                    let clone = parsed.clone();
+                    shared = Some((clone, start.elapsed()));
+                }
+                let (answer, solve_time) = match part {
+                    Part::Easy => timed_answer(|| Self::solve_easy(parsed))?,
+                    Part::Hard => timed_answer(|| Self::solve_hard(parsed))?,
+                };
+                Ok(Solution { answer, parse_time, solve_time, reused_parse })
+            });
+            results.push((part, result));
+        }
+        results
+    }
 
-        if include_hard {
-            let hard_parsed = parse_all(Self::parse_hard(), &raw_chars);
-            println!("Hard: {}", Self::solve_hard(hard_parsed));
+    // Prints the answers to `parts`, marked as right or wrong if `answers` has them, and with
+    // `timings`, how long each phase took.
+    fn run(filename : Option<String>, parts : &[Part], timings : bool, answers : &Answers) -> Result<Vec<(Part, Solution)>, AocError> {
+        let filename = filename.unwrap_or(default_input(Self::day_number()));
+        let (raw_chars, input_times) = load_input(&filename)?;
+        let mut solutions = Vec::new();
+        for (part, result) in Self::solve_parts(&filename, &raw_chars, parts) {
+            let solution = result?;
+            println!("{part}: {}{}", solution.answer, answers.mark(Self::day_number(), &filename, part, &solution.answer));
+            solutions.push((part, solution));
+        }
+        if timings {
+            println!("read: {} ms, chars: {} ms", millis(input_times.read), millis(input_times.chars));
+            for (part, solution) in &solutions {
+                let reused = if solution.reused_parse { " (cloned)" } else { "" };
+                println!("{part}: parse {} ms{reused}, solve {} ms", millis(solution.parse_time), millis(solution.solve_time));
+            }
         }
+        Ok(solutions)
     }
     
 }
+
+pub fn read_input(path : &str) -> Result<String, AocError> {
+    read_to_string(path).map_err(|source| AocError::Io { path: path.to_string(), source })
+}
+
+// Reads `path` as chars, timing both steps.
+pub fn load_input(path : &str) -> Result<(Vec<char>, InputTimes), AocError> {
+    let start = Instant::now();
+    let raw = read_input(path)?;
+    let read = start.elapsed();
+    let start = Instant::now();
+    let raw_chars = raw.chars().collect();
+    Ok((raw_chars, InputTimes { read, chars: start.elapsed() }))
+}
+
+// Parses all of `input` (read from `path`) and validates the result.
+pub fn parse_input<'a, D : Day + ?Sized>(parser : impl Parser<'a, char, O=D::Input>, path : &str, input : &'a [char]) -> Result<D::Input, AocError> {
+    let parsed = try_parse_all(parser, input).map_err(|err| AocError::parse(path, input, err))?;
+    D::validate(&parsed).map_err(AocError::Validation)?;
+    Ok(parsed)
+}
+
+fn timed_solve<'a, D : Day + ?Sized, A : fmt::Display>(parser : impl Parser<'a, char, O=D::Input>, solve : fn(D::Input) -> Result<A, String>, path : &str, input : &'a [char]) -> Result<Solution, AocError> {
+    let start = Instant::now();
+    let parsed = parse_input::<D>(parser, path, input)?;
+    let parse_time = start.elapsed();
+    let (answer, solve_time) = timed_answer(|| solve(parsed))?;
+    Ok(Solution { answer, parse_time, solve_time, reused_parse: false })
+}
+
+// Runs a solver with `catch_solver`, timing it but not the printing of its answer.
+fn timed_answer<A : fmt::Display>(solve : impl FnOnce() -> Result<A, String>) -> Result<(String, Duration), AocError> {
+    let start = Instant::now();
+    let answer = catch_solver(solve)?.map_err(|message| AocError::Solver { message, panicked: false })?;
+    let solve_time = start.elapsed();
+    Ok((answer.to_string(), solve_time))
+}
+
+thread_local! {
+    static CATCHING : Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs a solver, turning a panic into `AocError::Solver` with the panic's message. The panic hook
+// stays quiet for panics caught here, on any thread, and reports the rest as usual.
+pub fn catch_solver<A>(solve : impl FnOnce() -> A) -> Result<A, AocError> {
+    static QUIET_HOOK : Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                hook(info);
+            }
+        }));
+    });
+    CATCHING.set(true);
+    let res = catch_unwind(AssertUnwindSafe(solve));
+    CATCHING.set(false);
+    res.map_err(|payload| AocError::Solver { message: panic_message(&*payload), panicked: true })
+}
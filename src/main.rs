@@ -1,19 +1,226 @@
-use std::env::args;
+use std::{env::args, path::Path, process::exit};
 
-use aoc_2025::{Day, day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6};
+use aoc_2025::{Answers, AocError, DynDay, Part, Registry, Solution, answers::DEFAULT_ANSWERS, bench::{self, Baseline}, cli::{Command, USAGE, parse_args}, default_input, load_input, registry, scaffold, submit::{self, Attempts, DEFAULT_ATTEMPTS, Verdict}, summary::{self, Status}, web::Client};
+
+// Every puzzle, or just `day` if given.
+fn selected(registry : &Registry, day : Option<i32>) -> Result<Vec<&'static dyn DynDay>, AocError> {
+    match day {
+        Some(day) => registry.get(day).map(|d| vec![d]).ok_or_else(|| AocError::Usage(format!("day {day} isn't implemented"))),
+        None => Ok(registry.puzzles().collect()),
+    }
+}
 
 fn main() {
-    let args : Vec<_> = args().skip(1).take(3).collect();
+    let registry = registry();
+    for problem in registry.problems() {
+        eprintln!("warning: {problem}");
+    }
+    if let Err(e) = parse_args(args().skip(1)).and_then(|command| execute(&registry, command)) {
+        eprintln!("error: {e}");
+        if let AocError::Usage(_) = e {
+            eprintln!("run `aoc-2025 --help` for usage");
+        }
+        exit(e.exit_code());
+    }
+}
+
+fn execute(registry : &Registry, command : Command) -> Result<(), AocError> {
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::Run { day, parts, input, timings, record } => {
+            if input.is_none() && registry.puzzles().any(|puzzle| puzzle.number() == day) {
+                fetch_missing(&[day], false)?;
+            }
+            let path = input.unwrap_or(default_input(day));
+            let mut answers = Answers::load(DEFAULT_ANSWERS)?;
+            let solutions = selected(registry, Some(day))?[0].run(Some(path.clone()), &parts, timings, &answers)?;
+            if record {
+                for (part, solution) in solutions {
+                    answers.set(day, &path, part, solution.answer);
+                }
+                answers.save(DEFAULT_ANSWERS)?;
+                println!("recorded answers in {DEFAULT_ANSWERS}");
+            } else {
+                check(&answers, day, &path, &solutions)?;
+            }
+        }
+        Command::All { parallel, json, record } => {
+            let mut answers = Answers::load(DEFAULT_ANSWERS)?;
+            let days : Vec<&dyn DynDay> = registry.puzzles().collect();
+            fetch_missing(&days.iter().map(|day| day.number()).collect::<Vec<_>>(), false)?;
+            let rows = summary::run_all(&days, parallel);
+            println!("{}", if json { summary::json(&rows, &answers) } else { summary::table(&rows, &answers) });
+            if record {
+                for row in &rows {
+                    if let Ok(solution) = &row.result {
+                        answers.set(row.day, &default_input(row.day), row.part, solution.answer.clone());
+                    }
+                }
+                answers.save(DEFAULT_ANSWERS)?;
+                if !json {
+                    println!("recorded answers in {DEFAULT_ANSWERS}");
+                }
+            }
+            // The table already shows any errors and wrong answers, so just exit with the right code.
+            if let Some(Err(e)) = rows.iter().map(|row| &row.result).find(|result| result.is_err()) {
+                exit(e.exit_code());
+            }
+            if !record && rows.iter().any(|row| matches!(row.status(&answers), Status::Wrong { .. })) {
+                exit(AocError::WrongAnswer(String::new()).exit_code());
+            }
+        }
+        Command::List => {
+            for day in registry.all() {
+                let number = day.number();
+                if number == 0 {
+                    println!("day  0  (template)");
+                    continue;
+                }
+                let input = default_input(number);
+                let missing = if Path::new(&input).exists() { "" } else { " (missing)" };
+                println!("day {number:>2}  {input}{missing}");
+            }
+        }
+        Command::Bench { day, options } => {
+            let mut baseline = Baseline::load(&options.baseline)?;
+            let mut regressions = 0;
+            for selected_day in selected(registry, day)? {
+                let number = selected_day.number();
+                let path = default_input(number);
+                if day.is_none() && !Path::new(&path).exists() {
+                    println!("skipping day {number}: {path} not found");
+                    continue;
+                }
+                let stats = bench::bench_day(selected_day, &path, options.budget, options.warmup)?;
+                let (table, day_regressions) = bench::report(number, &stats, &baseline, options.threshold);
+                println!("{table}");
+                regressions += day_regressions;
+                if options.save_baseline {
+                    for (phase, stats) in bench::PHASES.iter().zip(&stats) {
+                        baseline.set(number, phase, stats.median);
+                    }
+                }
+            }
+            if options.save_baseline {
+                baseline.save(&options.baseline)?;
+                println!("saved baseline to {}", options.baseline);
+            }
+            if regressions > 0 {
+                return Err(AocError::Regression(format!("{regressions} phases regressed beyond {}%", options.threshold * 100.0)));
+            }
+        }
+        Command::Test { day } => {
+            let answers = Answers::load(DEFAULT_ANSWERS)?;
+            for selected_day in selected(registry, day)? {
+                let number = selected_day.number();
+                let path = format!("inputs/day{number}_test.txt");
+                if day.is_none() && !Path::new(&path).exists() {
+                    println!("skipping day {number}: {path} not found");
+                    continue;
+                }
+                println!("Day {number} (example)");
+                let solutions = selected_day.run(Some(path.clone()), &Part::BOTH, false, &answers)?;
+                check(&answers, number, &path, &solutions)?;
+            }
+        }
+        Command::Fetch { day } => {
+            let days : Vec<i32> = match day {
+                Some(day) => vec![day],
+                None => registry.puzzles().map(|day| day.number()).collect(),
+            };
+            fetch_missing(&days, true)?;
+        }
+        Command::Submit { day, part, force } => submit_answer(registry, day, part, force)?,
+        Command::Grammar { day, part, svg } => {
+            let grammar = selected(registry, Some(day))?[0].grammar(part);
+            let start = format!("day{day}");
+            if svg {
+                print!("{}", grammar.to_railroad_svg(&start));
+            } else {
+                print!("{}", grammar.to_ebnf(&start));
+            }
+        }
+        Command::New { day } => new_day(day)?,
+    }
+    Ok(())
+}
+
+// Fails if any of `solutions` differs from its accepted answer.
+fn check(answers : &Answers, day : i32, path : &str, solutions : &[(Part, Solution)]) -> Result<(), AocError> {
+    let wrong = solutions.iter()
+        .filter(|(part, solution)| answers.check(day, path, *part, &solution.answer) == Some(false))
+        .count();
+    match wrong {
+        0 => Ok(()),
+        _ => Err(AocError::WrongAnswer(format!("{wrong} wrong answers for day {day} on {path}"))),
+    }
+}
+
+// Downloads the inputs of `days` that aren't there yet. Unless `required`, this quietly does
+// nothing without a session token and only warns about failed downloads, leaving a missing input
+// to fail when it's read.
+fn fetch_missing(days : &[i32], required : bool) -> Result<(), AocError> {
+    let missing : Vec<i32> = days.iter().copied().filter(|day| !Path::new(&default_input(*day)).exists()).collect();
+    if missing.is_empty() {
+        if required {
+            println!("all inputs are already downloaded");
+        }
+        return Ok(());
+    }
+    let client = Client::from_config()?;
+    if !required && !client.has_session() {
+        return Ok(());
+    }
+    for day in missing {
+        let path = default_input(day);
+        match client.fetch_input(day, &path) {
+            Ok(_) => eprintln!("fetched {path}"),
+            Err(e) if !required => eprintln!("warning: couldn't fetch {path}: {e}"),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
 
-    let day_number : usize = args[0].parse().expect("first argument is day number");
-    
-    let include_hard = args.len() >= 2;
+// Solves `part` of `day` and submits the answer, unless it's known or likely to be wrong.
+fn submit_answer(registry : &Registry, day : i32, part : Part, force : bool) -> Result<(), AocError> {
+    let selected_day = selected(registry, Some(day))?[0];
+    fetch_missing(&[day], false)?;
+    let path = default_input(day);
+    let (raw_chars, _) = load_input(&path)?;
+    let answer = selected_day.solve_part(part, &path, &raw_chars)?.answer;
+    println!("Day {day} {part}: {answer}");
 
-    let mut filename : Option<String> = None;
-    if args.len() >= 3 {
-        filename = Some(args[2].clone());
+    let mut answers = Answers::load(DEFAULT_ANSWERS)?;
+    let mut attempts = Attempts::load(DEFAULT_ATTEMPTS)?;
+    let warnings = attempts.vet(day, part, &answer, answers.get(day, &path, part))?;
+    for warning in &warnings {
+        eprintln!("warning: {warning}");
+    }
+    if !warnings.is_empty() && !force {
+        return Err(AocError::Usage("not submitting; pass --force to submit anyway".to_string()));
     }
 
-    let days = [Day1::run, Day2::run, Day3::run, Day4::run, Day5::run, Day6::run];
-    days[day_number - 1](filename, include_hard);
+    let (verdict, message) = submit::submit(&Client::from_config()?, day, part, &answer)?;
+    attempts.record(day, part, verdict, &answer);
+    attempts.save(DEFAULT_ATTEMPTS)?;
+    println!("{message}");
+    match verdict {
+        Verdict::Correct => {
+            answers.set(day, &path, part, answer);
+            answers.save(DEFAULT_ANSWERS)?;
+            println!("recorded the answer in {DEFAULT_ANSWERS}");
+            Ok(())
+        }
+        Verdict::RateLimited => Err(AocError::Http("rate limited; try again later".to_string())),
+        _ => Err(AocError::WrongAnswer(format!("{answer} is {verdict}"))),
+    }
+}
+
+fn new_day(day : i32) -> Result<(), AocError> {
+    for path in scaffold::new_day(Path::new("."), day)? {
+        println!("created {path}");
+    }
+    println!("registered day{day}::Day{day} in src/lib.rs");
+    Ok(())
 }
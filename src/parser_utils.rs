@@ -1,16 +1,47 @@
-use std::{fmt::Debug, str::FromStr};
+use std::str::FromStr;
 
 use parser_combinators::*;
 
-pub fn chr<'a>(c: char) -> impl Parser<'a, char, O = &'a char> {
-    read().satisfy(move |c1| **c1 == c)
+use crate::AocError;
+
+pub struct Chr(char);
+
+impl<'a> Parser<'a, char> for Chr {
+    type O = &'a char;
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        read().satisfy(|c: &&char| **c == self.0).parse(tokens)
+    }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Literal(self.0.to_string())
+    }
+
+    fn generate(&self, g: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        let c = g.alphabet.iter().find(|c| **c == self.0)?;
+        out.push(*c);
+        Some(c)
+    }
+}
+
+impl<'a> Syntax<'a, char> for Chr {
+    fn print(&self, c: &&'a char, out: &mut Vec<char>) -> bool {
+        **c == self.0 && { out.push(self.0); true }
+    }
 }
 
-pub fn keyword<'a>(kw: &str) -> impl Parser<'a, char, O = ()> {
-    let kw: Vec<char> = kw.chars().collect();
-    move |tokens: &'a [char]| {
-        if tokens.starts_with(&kw) {
-            Ok(((), &tokens[kw.len()..]))
+pub fn chr<'a>(c: char) -> impl Syntax<'a, char, O = &'a char> {
+    Chr(c)
+}
+
+pub struct Keyword(Vec<char>);
+
+impl<'a> Parser<'a, char> for Keyword {
+    type O = ();
+
+    fn parse(&self, tokens: &'a [char]) -> ParseResult<'a, char, Self::O> {
+        if tokens.starts_with(&self.0) {
+            Ok(((), &tokens[self.0.len()..]))
         } else {
             Err(ParseError {
                 typ: ErrorType::Msg("keyword".to_string()),
@@ -18,6 +49,26 @@ pub fn keyword<'a>(kw: &str) -> impl Parser<'a, char, O = ()> {
             })
         }
     }
+
+    fn grammar(&self) -> Grammar {
+        Grammar::Literal(self.0.iter().collect())
+    }
+
+    fn generate(&self, _: &mut Gen<'a, char>, out: &mut Vec<char>) -> Option<Self::O> {
+        out.extend(&self.0);
+        Some(())
+    }
+}
+
+impl<'a> Syntax<'a, char> for Keyword {
+    fn print(&self, _: &(), out: &mut Vec<char>) -> bool {
+        out.extend(&self.0);
+        true
+    }
+}
+
+pub fn keyword<'a>(kw: &str) -> impl Syntax<'a, char, O = ()> {
+    Keyword(kw.chars().collect())
 }
 
 pub fn whitespace<'a>() -> impl Parser<'a, char, O = ()> {
@@ -25,25 +76,54 @@ pub fn whitespace<'a>() -> impl Parser<'a, char, O = ()> {
         .satisfy(|c: &&char| c.is_whitespace())
         .many()
         .map_const(())
+        .named("whitespace")
 }
 
 pub fn digit<'a>() -> impl Parser<'a, char, O = &'a char> {
-    read().satisfy(|c: &&char| c.is_digit(10))
+    read().satisfy(|c: &&char| c.is_ascii_digit()).named("digit")
 }
 
-pub fn nat<'a, N>() -> impl Parser<'a, char, O = N>
+pub fn nat<'a, N>() -> impl Syntax<'a, char, O = N>
 where
-    N : FromStr,
-    <N as FromStr>::Err: Debug,
+    N : FromStr + ToString,
 {
-    digit().many1().map(|ds| ds.into_iter().collect::<String>().parse().expect("digits parse to int"))
+    // Numbers too large for `N` fail to parse rather than panicking
+    digit().many1().try_map(|ds| ds.into_iter().collect::<String>().parse().ok())
+        .print_with(|n: &N| Some(n.to_string().chars().collect()))
+        .named("nat")
 }
 
-pub fn parse_all<'a, O, P : Parser<'a, char, O=O>>(parser : P, input : &'a [char]) -> O {
-    let (res, rest) = parser.parse(input).expect("parse succeeds");
+// Like `parse_all`, but returns an error for invalid input instead of panicking.
+pub fn try_parse_all<'a, O, P : Parser<'a, char, O=O>>(parser : P, input : &'a [char]) -> Result<O, ParseError<'a, char>> {
+    let (res, rest) = parser.parse(input)?;
     if !rest.is_empty() {
-        println!("{rest:?}");
-        panic!("parse should handle all input");
+        return Err(ParseError { typ: ErrorType::Msg("expected end of input".to_string()), rest });
+    }
+    Ok(res)
+}
+
+pub fn parse_all<'a, O, P : Parser<'a, char, O=O>>(parser : P, input : &'a [char]) -> O {
+    match try_parse_all(parser, input) {
+        Ok(res) => res,
+        Err(ParseError { typ, rest }) => {
+            println!("{rest:?}");
+            panic!("parse should handle all input: {typ:?}");
+        }
     }
-    res
+}
+
+// The parser for a whole input of type `T`, which may end in whitespace.
+pub fn from_parse<'a, T : FromParse>() -> impl Parser<'a, char, O = T> {
+    T::parser().seql(whitespace())
+}
+
+// Parses all of `text` as a `T`, picking the parser from the type, e.g. `parse_as::<Vec<u64>>(&text)`.
+// Errors point into `text`, which is called `name` in their message.
+pub fn parse_as<T : FromParse>(name : &str, text : &str) -> Result<T, AocError> {
+    let chars : Vec<char> = text.chars().collect();
+    try_parse_all(from_parse(), &chars).map_err(|err| AocError::parse(name, &chars, err))
+}
+
+pub fn print_to_string<'a, S : Syntax<'a, char>>(syntax : &S, value : &S::O) -> Option<String> {
+    print_to_vec(syntax, value).map(|chars| chars.into_iter().collect())
 }
@@ -0,0 +1,5 @@
+use parser_combinators::Parse;
+
+// An inclusive range of IDs, written `lo-hi`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Parse)]
+pub struct Range(#[parse(sep = "-")] pub u64, pub u64);
@@ -0,0 +1,96 @@
+use parser_combinators::{Grammar, Parser, Rng, sample};
+
+use crate::{Answers, AocError, Day, Part, Solution, parse_input};
+
+// An object-safe view of a `Day`, so that days can be kept in one list and picked by number at
+// runtime. Every `Day` is one, since days are unit structs.
+pub trait DynDay : Sync {
+    fn number(&self) -> i32;
+    fn solve_part(&self, part : Part, path : &str, input : &[char]) -> Result<Solution, AocError>;
+    fn solve_parts(&self, path : &str, input : &[char], parts : &[Part]) -> Vec<(Part, Result<Solution, AocError>)>;
+    fn run(&self, filename : Option<String>, parts : &[Part], timings : bool, answers : &Answers) -> Result<Vec<(Part, Solution)>, AocError>;
+    // Parses (and validates) one part's input without solving it.
+    fn parse(&self, part : Part, path : &str, input : &[char]) -> Result<(), AocError>;
+    // A random input from the grammar of part 1, see `parser_combinators::sample`.
+    fn sample(&self, rng : &mut Rng, size : usize) -> Option<Vec<char>>;
+    // The language one part's parser accepts.
+    fn grammar(&self, part : Part) -> Grammar;
+}
+
+impl<D : Day + Sync> DynDay for D {
+    fn number(&self) -> i32 {
+        D::day_number()
+    }
+
+    fn solve_part(&self, part : Part, path : &str, input : &[char]) -> Result<Solution, AocError> {
+        D::solve_part(part, path, input)
+    }
+
+    fn solve_parts(&self, path : &str, input : &[char], parts : &[Part]) -> Vec<(Part, Result<Solution, AocError>)> {
+        D::solve_parts(path, input, parts)
+    }
+
+    fn run(&self, filename : Option<String>, parts : &[Part], timings : bool, answers : &Answers) -> Result<Vec<(Part, Solution)>, AocError> {
+        D::run(filename, parts, timings, answers)
+    }
+
+    fn parse(&self, part : Part, path : &str, input : &[char]) -> Result<(), AocError> {
+        match (part, D::parse_hard()) {
+            (Part::Hard, Some(parser)) => parse_input::<D>(parser, path, input).map(drop),
+            _ => parse_input::<D>(D::parse_easy(), path, input).map(drop),
+        }
+    }
+
+    fn sample(&self, rng : &mut Rng, size : usize) -> Option<Vec<char>> {
+        sample(&D::parse_easy(), rng, size)
+    }
+
+    fn grammar(&self, part : Part) -> Grammar {
+        match (part, D::parse_hard()) {
+            (Part::Hard, Some(parser)) => parser.grammar(),
+            _ => D::parse_easy().grammar(),
+        }
+    }
+}
+
+// The registered days, sorted by number. Day 0 is the template for new days rather than a puzzle.
+pub struct Registry {
+    days : Vec<&'static dyn DynDay>,
+}
+
+impl Registry {
+    pub fn new(mut days : Vec<&'static dyn DynDay>) -> Self {
+        days.sort_by_key(|day| day.number());
+        Registry { days }
+    }
+
+    pub fn get(&self, number : i32) -> Option<&'static dyn DynDay> {
+        self.days.iter().find(|day| day.number() == number).copied()
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &'static dyn DynDay> + '_ {
+        self.days.iter().copied()
+    }
+
+    // The actual puzzles, leaving out the template.
+    pub fn puzzles(&self) -> impl Iterator<Item = &'static dyn DynDay> + '_ {
+        self.all().filter(|day| day.number() > 0)
+    }
+
+    // Days registered more than once, and days missing between day 1 and the last registered day.
+    pub fn problems(&self) -> Vec<String> {
+        let numbers : Vec<i32> = self.days.iter().map(|day| day.number()).collect();
+        let mut duplicates : Vec<i32> = numbers.windows(2).filter(|pair| pair[0] == pair[1]).map(|pair| pair[0]).collect();
+        duplicates.dedup();
+        let mut problems : Vec<String> = duplicates.into_iter()
+            .map(|number| format!("day {number} is registered more than once"))
+            .collect();
+        let last = numbers.last().copied().unwrap_or(0);
+        for number in 1..last {
+            if !numbers.contains(&number) {
+                problems.push(format!("day {number} isn't registered"));
+            }
+        }
+        problems
+    }
+}
@@ -0,0 +1,79 @@
+use std::{fs, path::Path};
+
+use crate::AocError;
+
+// Day 0 is the template for new days. It's an ordinary day that sums and counts a list of numbers,
+// so the compiler and its example test keep it working; `new_day` renames it and swaps in a stub
+// example, which fails until its answers are filled in.
+const TEMPLATE : &str = "src/day0.rs";
+const LIB : &str = "src/lib.rs";
+const TEMPLATE_EXAMPLE : &str = "example: file \"inputs/day0_test.txt\" => easy 6, hard 3;";
+
+fn io_error(path : &Path) -> impl FnOnce(std::io::Error) -> AocError + '_ {
+    move |source| AocError::Io { path: path.display().to_string(), source }
+}
+
+// The template with day 0 replaced by `day`, failing if the template no longer has the parts that
+// need replacing.
+fn instantiate(template : &str, day : i32) -> Result<String, AocError> {
+    let replacements = [
+        ("Day0".to_string(), format!("Day{day}")),
+        ("fn day_number() -> i32 {\n        0\n".to_string(), format!("fn day_number() -> i32 {{\n        {day}\n")),
+        (TEMPLATE_EXAMPLE.to_string(), format!("// TODO: fill in the example's answers.\n    example: file \"inputs/day{day}_test.txt\" => easy \"TODO\", hard \"TODO\";")),
+    ];
+    let mut code = template.to_string();
+    for (from, to) in replacements {
+        if !code.contains(&from) {
+            return Err(AocError::Validation(format!("{TEMPLATE} no longer contains {from:?}")));
+        }
+        code = code.replace(&from, &to);
+    }
+    Ok(code)
+}
+
+// `lib` with `dayN::DayN` added to its `days!`, keeping them in order.
+fn register(lib : &str, day : i32) -> Result<String, AocError> {
+    let entry = format!("    day{day}::Day{day},");
+    let start = lib.find("days!(\n").ok_or_else(|| AocError::Validation(format!("{LIB} has no `days!(`")))? + "days!(\n".len();
+    let end = start + lib[start..].find(");").ok_or_else(|| AocError::Validation(format!("{LIB}'s `days!(` isn't closed")))?;
+    let mut entries : Vec<&str> = lib[start..end].lines().collect();
+    if entries.contains(&entry.as_str()) {
+        return Err(AocError::Usage(format!("day {day} is already registered in {LIB}")));
+    }
+    let number = |entry : &str| entry.trim().strip_prefix("day")?.split("::").next()?.parse::<i32>().ok();
+    let at = entries.iter().position(|e| number(e).is_some_and(|n| n > day)).unwrap_or(entries.len());
+    entries.insert(at, &entry);
+    Ok(format!("{}{}\n{}", &lib[..start], entries.join("\n"), &lib[end..]))
+}
+
+// Creates `src/day<day>.rs` from the template with a stub example test, registers it in
+// `src/lib.rs`, and creates empty `inputs/day<day>.txt` and `inputs/day<day>_test.txt`. Nothing
+// is written if any of the files already exist. Returns the files it created.
+pub fn new_day(root : &Path, day : i32) -> Result<Vec<String>, AocError> {
+    if day < 1 {
+        return Err(AocError::Usage("day 0 is the template".to_string()));
+    }
+    let source = format!("src/day{day}.rs");
+    let inputs = [format!("inputs/day{day}.txt"), format!("inputs/day{day}_test.txt")];
+    let existing : Vec<&String> = [&source].into_iter().chain(&inputs).filter(|path| root.join(path).exists()).collect();
+    if !existing.is_empty() {
+        let existing : Vec<&str> = existing.iter().map(|path| path.as_str()).collect();
+        return Err(AocError::Usage(format!("won't overwrite {}", existing.join(", "))));
+    }
+
+    let template_path = root.join(TEMPLATE);
+    let template = fs::read_to_string(&template_path).map_err(io_error(&template_path))?;
+    let code = instantiate(&template, day)?;
+    let lib_path = root.join(LIB);
+    let lib = fs::read_to_string(&lib_path).map_err(io_error(&lib_path))?;
+    let lib = register(&lib, day)?;
+
+    let source_path = root.join(&source);
+    fs::write(&source_path, code).map_err(io_error(&source_path))?;
+    fs::write(&lib_path, lib).map_err(io_error(&lib_path))?;
+    for input in &inputs {
+        let path = root.join(input);
+        fs::write(&path, "").map_err(io_error(&path))?;
+    }
+    Ok([source].into_iter().chain(inputs).collect())
+}
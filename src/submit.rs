@@ -0,0 +1,196 @@
+use std::{fmt, fs, path::Path, time::{SystemTime, UNIX_EPOCH}};
+
+use parser_combinators::{Parser, pattern};
+
+use crate::{AocError, Part, parser_utils::{chr, try_parse_all}, web::Client};
+
+// Every answer ever submitted, so that none is submitted twice.
+pub const DEFAULT_ATTEMPTS : &str = "inputs/attempts.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without a hint which way.
+    Wrong,
+    // Submitted too soon after a wrong answer; the answer wasn't checked.
+    RateLimited,
+}
+
+impl Verdict {
+    const ALL : [Verdict; 5] = [Verdict::Correct, Verdict::TooHigh, Verdict::TooLow, Verdict::Wrong, Verdict::RateLimited];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate-limited",
+        }
+    }
+
+    fn from_name(name : &str) -> Option<Self> {
+        Verdict::ALL.into_iter().find(|verdict| verdict.name() == name)
+    }
+
+    // Whether the answer was checked and found wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name().replace('-', " "))
+    }
+}
+
+// The verdict in a response to an answer, by its wording.
+pub fn classify(body : &str) -> Option<Verdict> {
+    let body = body.to_lowercase();
+    if body.contains("that's the right answer") {
+        Some(Verdict::Correct)
+    } else if body.contains("answer too recently") {
+        Some(Verdict::RateLimited)
+    } else if body.contains("your answer is too high") {
+        Some(Verdict::TooHigh)
+    } else if body.contains("your answer is too low") {
+        Some(Verdict::TooLow)
+    } else if body.contains("not the right answer") {
+        Some(Verdict::Wrong)
+    } else {
+        None
+    }
+}
+
+// The text of a response's `<article>`, or of the whole page, without tags.
+pub fn message(body : &str) -> String {
+    let article = body.split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(body, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    // Seconds since the Unix epoch.
+    pub time : u64,
+    pub day : i32,
+    pub part : Part,
+    pub verdict : Verdict,
+    pub answer : String,
+}
+
+// The log of submitted answers, one `<time> <day> <part> <verdict> <answer>` line each.
+#[derive(Default)]
+pub struct Attempts {
+    attempts : Vec<Attempt>,
+}
+
+impl Attempts {
+    // No attempts if there's no file at `path` yet.
+    pub fn load(path : &str) -> Result<Self, AocError> {
+        if !Path::new(path).exists() {
+            return Ok(Attempts::default());
+        }
+        let raw_chars : Vec<char> = fs::read_to_string(path)
+            .map_err(|source| AocError::Io { path: path.to_string(), source })?
+            .chars().collect();
+        let line = pattern(r"(\d+) (\d{1,2}) ([12]) ([a-z-]+) (\S+)").map(|c| {
+            Ok(Attempt {
+                time: c.parse(1).ok_or("time out of range")?,
+                day: c.parse(2).expect("at most two digits"),
+                part: if c.string(3).as_deref() == Some("1") { Part::Easy } else { Part::Hard },
+                verdict: Verdict::from_name(&c.string(4).expect("group 4 always matches")).ok_or("unknown verdict")?,
+                answer: c.string(5).expect("group 5 always matches"),
+            })
+        });
+        let lines = try_parse_all(line.sep_end_by(chr('\n')), &raw_chars).map_err(|err| AocError::parse(path, &raw_chars, err))?;
+        let attempts = lines.into_iter().collect::<Result<_, &str>>()
+            .map_err(|message| AocError::Validation(format!("{path}: {message}")))?;
+        Ok(Attempts { attempts })
+    }
+
+    pub fn save(&self, path : &str) -> Result<(), AocError> {
+        let text : String = self.attempts.iter()
+            .map(|a| format!("{} {} {} {} {}\n", a.time, a.day, a.part.number(), a.verdict.name(), a.answer))
+            .collect();
+        fs::write(path, text).map_err(|source| AocError::Io { path: path.to_string(), source })
+    }
+
+    pub fn record(&mut self, day : i32, part : Part, verdict : Verdict, answer : &str) {
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |t| t.as_secs());
+        self.attempts.push(Attempt { time, day, part, verdict, answer: answer.to_string() });
+    }
+
+    pub fn for_part(&self, day : i32, part : Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.day == day && a.part == part)
+    }
+
+    // Refuses `answer` if it's known to be wrong or the part is already solved, according to these
+    // attempts or to `accepted`, the answer in the answers file. Otherwise returns warnings about
+    // it, such as contradicting an earlier "too high" or "too low".
+    pub fn vet(&self, day : i32, part : Part, answer : &str, accepted : Option<&str>) -> Result<Vec<String>, AocError> {
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(AocError::Validation(format!("won't submit {answer:?}")));
+        }
+        if let Some(correct) = self.for_part(day, part).find(|a| a.verdict == Verdict::Correct) {
+            return Err(AocError::Usage(format!("day {day} part {} is already solved: {}", part.number(), correct.answer)));
+        }
+        if let Some(accepted) = accepted {
+            return Err(AocError::Usage(format!("day {day} part {} is already solved: {accepted} is the accepted answer", part.number())));
+        }
+        if let Some(wrong) = self.for_part(day, part).find(|a| a.answer == answer && a.verdict.is_wrong()) {
+            return Err(AocError::WrongAnswer(format!("{answer} was already submitted and was {}", wrong.verdict)));
+        }
+
+        let Ok(guess) = answer.parse::<i128>() else {
+            return Ok(Vec::new());
+        };
+        let numbers = |verdict : Verdict| self.for_part(day, part)
+            .filter(move |a| a.verdict == verdict)
+            .filter_map(|a| a.answer.parse::<i128>().ok());
+        let mut warnings = Vec::new();
+        if let Some(high) = numbers(Verdict::TooHigh).min() && guess >= high {
+            warnings.push(format!("{answer} isn't below {high}, which was too high"));
+        }
+        if let Some(low) = numbers(Verdict::TooLow).max() && guess <= low {
+            warnings.push(format!("{answer} isn't above {low}, which was too low"));
+        }
+        Ok(warnings)
+    }
+}
+
+// Escapes `s` for an `application/x-www-form-urlencoded` body.
+fn form_encode(s : &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+// Posts `answer` for `part` of `day`, returning the verdict and the server's message.
+pub fn submit(client : &Client, day : i32, part : Part, answer : &str) -> Result<(Verdict, String), AocError> {
+    let form = format!("level={}&answer={}", part.number(), form_encode(answer));
+    let response = client.request(&format!("/day/{day}/answer"), Some(&form))?;
+    let message = message(&response.body);
+    match (response.status, classify(&response.body)) {
+        (200, Some(verdict)) => Ok((verdict, message)),
+        (200, None) => Err(AocError::Http(format!("couldn't tell whether {answer} was right: {message}"))),
+        (status, _) => Err(AocError::Http(format!("unexpected status {status} submitting day {day} part {}", part.number()))),
+    }
+}
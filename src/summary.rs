@@ -0,0 +1,184 @@
+use std::{sync::{Arc, Mutex, atomic::{AtomicUsize, Ordering}}, thread};
+
+use crate::{Answers, AocError, DynDay, InputTimes, Part, Solution, default_input, load_input, millis};
+
+// The outcome of one part of one day. Errors are shared because a day whose input can't be read
+// fails both parts with the same error.
+pub struct Row {
+    pub day : i32,
+    pub part : Part,
+    // Reading the input is shared between the parts, so only the first part of a day has these.
+    pub input_times : Option<InputTimes>,
+    pub result : Result<Solution, Arc<AocError>>,
+}
+
+impl Row {
+    // The accepted answer for this row, if there is one.
+    pub fn expected<'a>(&self, answers : &'a Answers) -> Option<&'a str> {
+        answers.get(self.day, &default_input(self.day), self.part)
+    }
+
+    pub fn status<'a>(&self, answers : &'a Answers) -> Status<'a> {
+        match (&self.result, self.expected(answers)) {
+            (Ok(solution), Some(expected)) if solution.answer == expected => Status::Correct,
+            (Ok(_), Some(expected)) => Status::Wrong { expected },
+            (Ok(_), None) => Status::Unchecked,
+            (Err(e), _) => Status::Failed(e.kind()),
+        }
+    }
+}
+
+// How a row's answer compares with the accepted one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status<'a> {
+    Correct,
+    Wrong { expected : &'a str },
+    // Solved, but there's no accepted answer to compare with.
+    Unchecked,
+    // Not solved, with the kind of error.
+    Failed(&'static str),
+}
+
+impl Status<'_> {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong { .. } => "wrong",
+            Status::Unchecked => "ok",
+            Status::Failed(kind) => kind,
+        }
+    }
+}
+
+fn run_day(day : &dyn DynDay) -> Vec<Row> {
+    let path = default_input(day.number());
+    let (raw_chars, input_times) = match load_input(&path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            let e = Arc::new(e);
+            return Part::BOTH.into_iter()
+                .map(|part| Row { day: day.number(), part, input_times: None, result: Err(e.clone()) })
+                .collect();
+        }
+    };
+    day.solve_parts(&path, &raw_chars, &Part::BOTH).into_iter()
+        .enumerate()
+        .map(|(i, (part, result))| Row {
+            day: day.number(),
+            part,
+            input_times: (i == 0).then_some(input_times),
+            result: result.map_err(Arc::new),
+        })
+        .collect()
+}
+
+// Solves both parts of every day in `days`, in order. With `parallel`, days are shared out between
+// as many threads as there are cores.
+pub fn run_all(days : &[&dyn DynDay], parallel : bool) -> Vec<Row> {
+    if !parallel {
+        return days.iter().flat_map(|day| run_day(*day)).collect();
+    }
+    let workers = thread::available_parallelism().map_or(1, usize::from).min(days.len());
+    let next = AtomicUsize::new(0);
+    let results : Mutex<Vec<(usize, Vec<Row>)>> = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(i) else {
+                        break;
+                    };
+                    let rows = run_day(*day);
+                    results.lock().expect("no worker panics while holding the lock").push((i, rows));
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().expect("workers are done");
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().flat_map(|(_, rows)| rows).collect()
+}
+
+pub fn table(rows : &[Row], answers : &Answers) -> String {
+    let mut lines = vec![format!(
+        "{:>3}  {:>4}  {:>20}  {:>9}  {:>9}  {:>10}  {:>10}  status",
+        "day", "part", "answer", "read ms", "chars ms", "parse ms", "solve ms",
+    )];
+    for row in rows {
+        let (read, chars) = match row.input_times {
+            Some(times) => (millis(times.read), millis(times.chars)),
+            None => (String::new(), String::new()),
+        };
+        let (answer, parse, solve) = match &row.result {
+            Ok(solution) => {
+                // A part that solved a clone of another's parse didn't really parse anything.
+                let reused = if solution.reused_parse { "*" } else { "" };
+                (solution.answer.clone(), format!("{reused}{}", millis(solution.parse_time)), millis(solution.solve_time))
+            }
+            Err(_) => ("-".to_string(), "-".to_string(), "-".to_string()),
+        };
+        lines.push(format!(
+            "{:>3}  {:>4}  {answer:>20}  {read:>9}  {chars:>9}  {parse:>10}  {solve:>10}  {}",
+            row.day, row.part.number(), match row.status(answers) {
+                Status::Correct => "✓".to_string(),
+                Status::Wrong { expected } => format!("✗ (expected {expected})"),
+                status => status.name().to_string(),
+            },
+        ));
+    }
+    if rows.iter().any(|row| row.result.as_ref().is_ok_and(|solution| solution.reused_parse)) {
+        lines.push("* cloned from part 1's parse".to_string());
+    }
+    // Errors go below the table, where they don't break up its columns. An unreadable input is
+    // only reported once, not for each part.
+    let mut last_error : Option<&Arc<AocError>> = None;
+    for row in rows {
+        if let Err(e) = &row.result && !last_error.is_some_and(|last| Arc::ptr_eq(last, e)) {
+            lines.push(format!("day {} part {}: {e}", row.day, row.part.number()));
+            last_error = Some(e);
+        }
+    }
+    lines.join("\n")
+}
+
+fn json_string(s : &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// One object per row. Answers are strings, since they may not fit in a JSON number's exact range.
+pub fn json(rows : &[Row], answers : &Answers) -> String {
+    let objects : Vec<String> = rows.iter()
+        .map(|row| {
+            let fields = match &row.result {
+                Ok(solution) => format!(
+                    "\"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"reused_parse\": {}",
+                    json_string(&solution.answer), solution.parse_time.as_nanos(), solution.solve_time.as_nanos(),
+                    solution.reused_parse,
+                ),
+                Err(e) => format!("\"error\": {}", json_string(&e.to_string())),
+            };
+            let fields = match row.expected(answers) {
+                Some(expected) => format!("{fields}, \"expected\": {}", json_string(expected)),
+                None => fields,
+            };
+            let fields = match row.input_times {
+                Some(times) => format!("\"read_ns\": {}, \"chars_ns\": {}, {fields}", times.read.as_nanos(), times.chars.as_nanos()),
+                None => fields,
+            };
+            format!("  {{\"day\": {}, \"part\": {}, \"status\": {}, {fields}}}", row.day, row.part.number(), json_string(row.status(answers).name()))
+        })
+        .collect();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
@@ -0,0 +1,231 @@
+use std::{env, fs, io::{Read, Write}, net::TcpStream, path::{Path, PathBuf}, process::{self, Stdio}, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use parser_combinators::{Parser, pattern};
+
+use crate::{AocError, parser_utils::{chr, try_parse_all}};
+
+pub const DEFAULT_BASE_URL : &str = "https://adventofcode.com/2025";
+// Holds the session token, so it's ignored by git.
+pub const DEFAULT_CONFIG : &str = "aoc.toml";
+// When the last request was made, so that throttling works across runs.
+pub const DEFAULT_STATE : &str = "target/aoc/last-request";
+const DEFAULT_THROTTLE : Duration = Duration::from_secs(5);
+const TIMEOUT : Duration = Duration::from_secs(30);
+const USER_AGENT : &str = "aoc-2025 (std-only input fetcher)";
+
+// Where the puzzle server is and how to talk to it, from the config file:
+//
+//     base_url = "http://localhost:8080/2025"
+//     session = "53616c7465645f5f..."
+//     throttle_ms = 5000
+//
+// All the keys are optional, and `AOC_BASE_URL` and `AOC_SESSION` override the file.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub base_url : String,
+    pub session : Option<String>,
+    // The least time between two requests.
+    pub throttle : Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { base_url: DEFAULT_BASE_URL.to_string(), session: None, throttle: DEFAULT_THROTTLE }
+    }
+}
+
+impl Config {
+    pub fn load(path : &str) -> Result<Self, AocError> {
+        let mut config = Config::default();
+        if Path::new(path).exists() {
+            let raw_chars : Vec<char> = fs::read_to_string(path)
+                .map_err(|source| AocError::Io { path: path.to_string(), source })?
+                .chars().collect();
+            let setting = pattern(r#"(\w+)[ \t]*=[ \t]*(?:(\d+)|"([^"\n]*)")"#)
+                .map(|c| (c.string(1).expect("group 1 always matches"), c.string(2).or(c.string(3)).expect("one of the values matches")));
+            let line = pattern("[ \t]*")
+                .seqr(setting.optional())
+                .seql(pattern("[ \t]*(?:#[^\n]*)?"));
+            let lines = try_parse_all(line.sep_by(chr('\n')), &raw_chars).map_err(|err| AocError::parse(path, &raw_chars, err))?;
+            for (key, value) in lines.into_iter().flatten() {
+                match key.as_str() {
+                    "base_url" => config.base_url = value,
+                    "session" => config.session = Some(value),
+                    "throttle_ms" => {
+                        let millis = value.parse().map_err(|_| AocError::Validation(format!("{path}: throttle_ms must be a number")))?;
+                        config.throttle = Duration::from_millis(millis);
+                    }
+                    _ => return Err(AocError::Validation(format!("{path}: unknown setting `{key}`"))),
+                }
+            }
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        config.base_url = config.base_url.trim_end_matches('/').to_string();
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status : u16,
+    pub body : String,
+}
+
+// The parts of an `http://` or `https://` URL that matter for making a request.
+struct Url<'a> {
+    secure : bool,
+    host : &'a str,
+    port : u16,
+    path : &'a str,
+}
+
+fn parse_url(url : &str) -> Result<Url<'_>, AocError> {
+    let invalid = || AocError::Usage(format!("`{url}` isn't an http:// or https:// URL"));
+    let (secure, rest) = match url.split_once("://") {
+        Some(("http", rest)) => (false, rest),
+        Some(("https", rest)) => (true, rest),
+        _ => return Err(invalid()),
+    };
+    let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+        None => (authority, if secure { 443 } else { 80 }),
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Url { secure, host, port, path })
+}
+
+// Splits a raw HTTP response into its status and body, skipping the headers.
+fn parse_response(url : &str, raw : &[u8]) -> Result<Response, AocError> {
+    let raw = String::from_utf8_lossy(raw);
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap_or((&raw, ""));
+    let status = head.split_whitespace().nth(1).and_then(|status| status.parse().ok())
+        .ok_or_else(|| AocError::Http(format!("{url}: malformed response: {:?}", head.lines().next().unwrap_or(""))))?;
+    Ok(Response { status, body: body.to_string() })
+}
+
+// Makes requests to the server in `config`, at most one per `config.throttle`.
+pub struct Client {
+    config : Config,
+    state : PathBuf,
+}
+
+impl Client {
+    pub fn new(config : Config, state : impl Into<PathBuf>) -> Self {
+        Client { config, state: state.into() }
+    }
+
+    pub fn from_config() -> Result<Self, AocError> {
+        Ok(Client::new(Config::load(DEFAULT_CONFIG)?, DEFAULT_STATE))
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.config.session.is_some()
+    }
+
+    fn session(&self) -> Result<&str, AocError> {
+        self.config.session.as_deref()
+            .ok_or_else(|| AocError::Usage(format!("no session token; set AOC_SESSION or `session` in {DEFAULT_CONFIG}")))
+    }
+
+    // Waits until `config.throttle` has passed since the last request, then records this one.
+    fn throttle(&self) -> Result<(), AocError> {
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        // The state only has whole milliseconds, so the last request may have been up to one later.
+        let last = fs::read_to_string(&self.state).ok()
+            .and_then(|millis| millis.trim().parse::<u64>().ok())
+            .map(|millis| Duration::from_millis(millis + 1));
+        if let Some(wait) = last.and_then(|last| (last + self.config.throttle).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+        let io_error = |source| AocError::Io { path: self.state.display().to_string(), source };
+        if let Some(dir) = self.state.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&self.state, now().as_millis().to_string()).map_err(io_error)
+    }
+
+    // Sends a request for `path`, relative to the base URL, posting `form` if there is one.
+    pub fn request(&self, path : &str, form : Option<&str>) -> Result<Response, AocError> {
+        let session = self.session()?;
+        let full_url = format!("{}{path}", self.config.base_url);
+        let url = parse_url(&full_url)?;
+        self.throttle()?;
+        let raw = if url.secure { curl(&full_url, session, form)? } else { send(&url, session, form)? };
+        parse_response(&full_url, &raw)
+    }
+
+    // Downloads `day`'s input to `path`, unless it's already there. Returns whether it did.
+    pub fn fetch_input(&self, day : i32, path : &str) -> Result<bool, AocError> {
+        if Path::new(path).exists() {
+            return Ok(false);
+        }
+        let response = self.request(&format!("/day/{day}/input"), None)?;
+        match response.status {
+            200 => {}
+            404 => return Err(AocError::Http(format!("day {day}'s input isn't available yet"))),
+            400 | 401 | 403 | 500 => return Err(AocError::Http(format!("the server rejected the session token ({})", response.status))),
+            status => return Err(AocError::Http(format!("unexpected status {status} fetching day {day}'s input"))),
+        }
+        // Written to a temporary file first so that an interrupted download isn't cached.
+        let partial = format!("{path}.part");
+        let io_error = |path : &str, source| AocError::Io { path: path.to_string(), source };
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(|e| io_error(path, e))?;
+        }
+        fs::write(&partial, response.body).map_err(|e| io_error(&partial, e))?;
+        fs::rename(&partial, path).map_err(|e| io_error(path, e))?;
+        Ok(true)
+    }
+}
+
+// A plain HTTP/1.0 request, so that the response isn't chunked and ends when the connection closes.
+fn send(url : &Url, session : &str, form : Option<&str>) -> Result<Vec<u8>, AocError> {
+    let http_error = |e : std::io::Error| AocError::Http(format!("{}:{}: {e}", url.host, url.port));
+    let mut stream = TcpStream::connect((url.host, url.port)).map_err(http_error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(http_error)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(http_error)?;
+    let mut request = format!(
+        "{} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: {USER_AGENT}\r\nCookie: session={session}\r\n",
+        if form.is_some() { "POST" } else { "GET" }, url.path, url.host,
+    );
+    if let Some(form) = form {
+        request.push_str(&format!("Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n", form.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(form.unwrap_or(""));
+    stream.write_all(request.as_bytes()).map_err(http_error)?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(http_error)?;
+    Ok(raw)
+}
+
+// std has no TLS, so HTTPS goes through curl. The session is passed on stdin, where other
+// processes can't see it.
+fn curl(url : &str, session : &str, form : Option<&str>) -> Result<Vec<u8>, AocError> {
+    let mut args = vec!["--silent", "--show-error", "--include", "--max-time", "30", "--user-agent", USER_AGENT, "--config", "-"];
+    if let Some(form) = form {
+        args.extend(["--data-raw", form]);
+    }
+    args.push(url);
+    let mut child = process::Command::new("curl").args(&args)
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AocError::Http(format!("couldn't run curl for {url}: {e}")))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(format!("header = \"Cookie: session={session}\"\n").as_bytes())
+        .map_err(|e| AocError::Http(format!("curl: {e}")))?;
+    drop(stdin);
+    let output = child.wait_with_output().map_err(|e| AocError::Http(format!("curl: {e}")))?;
+    if !output.status.success() {
+        return Err(AocError::Http(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(output.stdout)
+}
@@ -0,0 +1,69 @@
+use std::fs;
+
+use aoc_2025::{Answers, Part};
+use common::scratch;
+
+mod common;
+
+#[test]
+fn save_and_load_round_trip() {
+    let path = scratch("answers", "round-trip").join("answers.toml");
+    let path = path.to_str().unwrap();
+    let mut answers = Answers::default();
+    answers.set(1, "inputs/day1.txt", Part::Easy, "1055".to_string());
+    answers.set(1, "inputs/day1.txt", Part::Hard, "ABC def".to_string());
+    answers.set(12, "other input.txt", Part::Hard, String::new());
+    answers.set(2, "inputs/day2.txt", Part::Easy, "007".to_string());
+    answers.save(path).unwrap();
+
+    let loaded = Answers::load(path).unwrap();
+    assert_eq!(loaded.get(1, "inputs/day1.txt", Part::Easy), Some("1055"));
+    assert_eq!(loaded.get(1, "inputs/day1.txt", Part::Hard), Some("ABC def"));
+    assert_eq!(loaded.get(12, "other input.txt", Part::Hard), Some(""));
+    assert_eq!(loaded.get(2, "inputs/day2.txt", Part::Easy), Some("007"));
+    assert_eq!(loaded.get(2, "inputs/day2.txt", Part::Hard), None);
+    loaded.save(path).unwrap();
+    assert_eq!(Answers::load(path).unwrap().get(1, "inputs/day1.txt", Part::Hard), Some("ABC def"));
+}
+
+#[test]
+fn save_refuses_quotes_and_newlines() {
+    let path = scratch("answers", "refuse").join("answers.toml");
+    let path = path.to_str().unwrap();
+    for (input, answer) in [("inputs/day1.txt", "say \"hi\""), ("inputs/day1.txt", "two\nlines"), ("odd\".txt", "1")] {
+        let mut answers = Answers::default();
+        answers.set(1, input, Part::Easy, answer.to_string());
+        let err = answers.save(path).unwrap_err();
+        assert_eq!(err.kind(), "validation", "{err}");
+        assert!(!fs::exists(path).unwrap(), "{input:?} {answer:?}");
+    }
+}
+
+#[test]
+fn load_reads_comments_and_rejects_stray_answers() {
+    let dir = scratch("answers", "load");
+    let path = dir.join("answers.toml");
+    let path = path.to_str().unwrap();
+    fs::write(path, "# comment\n  [day3.\"in.txt\"]  # trailing\npart2 = \"x\"\n\tpart1=42\n").unwrap();
+    let answers = Answers::load(path).unwrap();
+    assert_eq!((answers.get(3, "in.txt", Part::Easy), answers.get(3, "in.txt", Part::Hard)), (Some("42"), Some("x")));
+    assert!(Answers::load(dir.join("missing.toml").to_str().unwrap()).unwrap().get(3, "in.txt", Part::Easy).is_none());
+
+    fs::write(path, "part1 = 1\n").unwrap();
+    assert!(Answers::load(path).is_err_and(|err| err.kind() == "validation"));
+    fs::write(path, "[day3.\"in.txt\"]\npart3 = 1\n").unwrap();
+    assert!(Answers::load(path).is_err_and(|err| err.kind() == "parse"));
+}
+
+#[test]
+fn check_and_mark_compare_with_the_accepted_answer() {
+    let mut answers = Answers::default();
+    answers.set(5, "in.txt", Part::Easy, "513".to_string());
+    assert_eq!(answers.check(5, "in.txt", Part::Easy, "513"), Some(true));
+    assert_eq!(answers.check(5, "in.txt", Part::Easy, "512"), Some(false));
+    assert_eq!(answers.check(5, "in.txt", Part::Hard, "513"), None);
+    assert_eq!(answers.check(5, "other.txt", Part::Easy, "513"), None);
+    assert_eq!(answers.mark(5, "in.txt", Part::Easy, "513"), " ✓");
+    assert_eq!(answers.mark(5, "in.txt", Part::Easy, "512"), " ✗ (expected 513)");
+    assert_eq!(answers.mark(5, "in.txt", Part::Hard, "1"), "");
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use aoc_2025::bench::{Baseline, PHASES, Stats, regression, report};
+
+fn ms(millis : &[u64]) -> Vec<Duration> {
+    millis.iter().map(|m| Duration::from_millis(*m)).collect()
+}
+
+fn stats(median : Duration) -> Stats {
+    Stats::new(&[median])
+}
+
+fn close(a : Duration, b : Duration) -> bool {
+    a.abs_diff(b) < Duration::from_nanos(10)
+}
+
+#[test]
+fn stats_summarise_samples() {
+    let stats = Stats::new(&ms(&[13, 100, 11, 10, 12]));
+    assert_eq!((stats.samples, stats.min, stats.median), (5, Duration::from_millis(10), Duration::from_millis(12)));
+    assert!(close(stats.mean, Duration::from_micros(29_200)), "{:?}", stats.mean);
+    // The population standard deviation, sqrt(1254.16) ms.
+    assert!(close(stats.stddev, Duration::from_secs_f64(1254.16_f64.sqrt() / 1000.0)), "{:?}", stats.stddev);
+    // The quartiles are 11 and 13 ms, so the fences are at 8 and 16 ms.
+    assert_eq!(stats.outliers, 1);
+}
+
+#[test]
+fn outliers_are_beyond_tukeys_fences() {
+    assert_eq!(Stats::new(&ms(&[5, 10, 10, 10, 10, 10, 16])).outliers, 2);
+    assert_eq!(Stats::new(&ms(&[1, 2, 3, 4, 5, 6, 7])).outliers, 0);
+    let one = Stats::new(&ms(&[7]));
+    assert_eq!((one.median, one.stddev, one.outliers), (Duration::from_millis(7), Duration::ZERO, 0));
+}
+
+#[test]
+#[should_panic(expected = "stats need at least one sample")]
+fn stats_need_samples() {
+    Stats::new(&[]);
+}
+
+#[test]
+fn regressions_are_beyond_the_threshold_and_the_noise() {
+    let base = Duration::from_millis(1);
+    let slower = regression(&stats(Duration::from_micros(1200)), base, 0.1);
+    assert!(slower.is_some_and(|change| (change - 0.2).abs() < 1e-9), "{slower:?}");
+    assert_eq!(regression(&stats(Duration::from_micros(1200)), base, 0.25), None);
+    assert_eq!(regression(&stats(Duration::from_micros(900)), base, 0.1), None);
+    // Tripling a 10 µs phase is under the 50 µs of noise, but octupling it isn't.
+    let base = Duration::from_micros(10);
+    assert_eq!(regression(&stats(Duration::from_micros(30)), base, 0.1), None);
+    assert!(regression(&stats(Duration::from_micros(80)), base, 0.1).is_some());
+}
+
+#[test]
+fn report_counts_regressions_against_the_baseline() {
+    let mut baseline = Baseline::default();
+    for phase in PHASES {
+        baseline.set(3, phase, Duration::from_millis(1));
+    }
+    let medians = [1, 2, 1, 1, 5, 1].map(|m| stats(Duration::from_millis(m)));
+    let (table, regressions) = report(3, &medians, &baseline, 0.1);
+    assert_eq!(regressions, 2);
+    assert!(table.contains("+100.0% REGRESSION") && table.contains("+400.0% REGRESSION"), "{table}");
+    assert_eq!(report(4, &medians, &baseline, 0.1).1, 0);
+}
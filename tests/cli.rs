@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use aoc_2025::{Part, bench::Budget, cli::{BenchOptions, Command, parse_args}};
+
+fn parse(line : &str) -> Result<Command, String> {
+    parse_args(line.split_whitespace().map(String::from)).map_err(|e| {
+        assert_eq!(e.kind(), "usage", "{line:?}: {e}");
+        e.to_string()
+    })
+}
+
+#[test]
+fn parses_each_command() {
+    let run = |day, parts : &[Part], input : Option<&str>, timings, record| Command::Run {
+        day, parts: parts.to_vec(), input: input.map(String::from), timings, record,
+    };
+    let cases = [
+        ("run 3", run(3, &Part::BOTH, None, false, false)),
+        ("3 --part 2 --timings", run(3, &[Part::Hard], None, true, false)),
+        ("run 25 --input x.txt --part both --record", run(25, &Part::BOTH, Some("x.txt"), false, true)),
+        ("all --json --parallel", Command::All { parallel: true, json: true, record: false }),
+        ("list", Command::List),
+        ("test", Command::Test { day: None }),
+        ("fetch 6", Command::Fetch { day: Some(6) }),
+        ("run 0", run(0, &Part::BOTH, None, false, false)),
+        ("submit 4 2 --force", Command::Submit { day: 4, part: Part::Hard, force: true }),
+        ("grammar 5 --part 2 --svg", Command::Grammar { day: 5, part: Part::Hard, svg: true }),
+        ("new 7", Command::New { day: 7 }),
+        ("help", Command::Help),
+    ];
+    for (line, command) in cases {
+        assert_eq!(parse(line), Ok(command), "{line:?}");
+    }
+}
+
+#[test]
+fn parses_bench_options() {
+    let Ok(Command::Bench { day: None, options }) = parse("bench") else {
+        panic!("bench");
+    };
+    assert_eq!(options.budget, Budget::Time(Duration::from_secs(1)));
+    assert_eq!((options.warmup, options.save_baseline, options.threshold), (1, false, 0.1));
+    let options = BenchOptions { budget: Budget::Iterations(5), warmup: 0, baseline: "b.txt".to_string(), save_baseline: true, threshold: 0.25 };
+    assert_eq!(
+        parse("bench 2 --iterations 5 --warmup 0 --baseline b.txt --save-baseline --threshold 25"),
+        Ok(Command::Bench { day: Some(2), options })
+    );
+}
+
+#[test]
+fn help_wins_anywhere() {
+    for line in ["-h", "--help", "run 3 --help", "bench --iterations -h", "nonsense --help"] {
+        assert_eq!(parse(line), Ok(Command::Help), "{line:?}");
+    }
+}
+
+#[test]
+fn rejects_bad_day_numbers() {
+    for (line, message) in [
+        ("run 26", "`26` isn't a day number (0 to 25)"),
+        ("9999999999", "`9999999999` isn't a day number (0 to 25)"),
+        ("test x", "`x` isn't a day number (0 to 25)"),
+        ("run", "`run` needs a day number"),
+        ("new --force", "`new` needs a day number"),
+        ("submit", "`submit` needs a day number"),
+        ("grammar", "`grammar` needs a day number"),
+        ("fetch 0", "day 0 is the template, which `fetch` can't be used on"),
+        ("submit 0 1", "day 0 is the template, which `submit` can't be used on"),
+    ] {
+        assert_eq!(parse(line), Err(message.to_string()), "{line:?}");
+    }
+}
+
+#[test]
+fn rejects_unknown_commands_flags_and_arguments() {
+    for (line, message) in [
+        ("", "missing command"),
+        ("frobnicate", "unknown command `frobnicate`"),
+        ("run 1 --verbose", "unknown option `--verbose` for `run`"),
+        ("all --timings", "unknown option `--timings` for `all`"),
+        ("bench --json", "unknown option `--json` for `bench`"),
+        ("submit 1 1 --record", "unknown option `--record` for `submit`"),
+        ("grammar 1 --json", "unknown option `--json` for `grammar`"),
+        ("list 3", "unexpected argument `3`"),
+        ("test 1 2", "unexpected argument `2`"),
+    ] {
+        assert_eq!(parse(line), Err(message.to_string()), "{line:?}");
+    }
+}
+
+#[test]
+fn rejects_bad_part_values() {
+    for (line, message) in [
+        ("run 1 --part 3", "`--part` must be 1, 2 or both, not `3`"),
+        ("run 1 --part easy", "`--part` must be 1, 2 or both, not `easy`"),
+        ("grammar 1 --part both", "`--part` must be 1 or 2 for `grammar`"),
+        ("submit 1 both", "the part must be 1 or 2, not `both`"),
+        ("submit 1", "`submit` needs a part, 1 or 2"),
+    ] {
+        assert_eq!(parse(line), Err(message.to_string()), "{line:?}");
+    }
+}
+
+#[test]
+fn rejects_missing_and_bad_values() {
+    for (line, message) in [
+        ("run 1 --part", "--part needs a value"),
+        ("run 1 --input", "--input needs a value"),
+        ("grammar 1 --part", "--part needs a value"),
+        ("bench --baseline", "--baseline needs a value"),
+        ("bench --iterations", "--iterations needs a value"),
+        ("bench --iterations 0", "`--iterations` must be at least 1"),
+        ("bench --time 0", "`--time` must be at least 1"),
+        ("bench --time soon", "`--time` must be a number, not `soon`"),
+        ("bench --warmup -1", "`--warmup` must be a number, not `-1`"),
+        ("bench --threshold -5", "`--threshold` must be at least 0"),
+        ("bench --threshold lots", "`--threshold` must be a number, not `lots`"),
+    ] {
+        assert_eq!(parse(line), Err(message.to_string()), "{line:?}");
+    }
+}
@@ -0,0 +1,53 @@
+// Helpers shared by the test files, each of which uses only some of them.
+#![allow(dead_code)]
+
+use std::{fs, io::{Read, Write}, net::TcpListener, path::{Path, PathBuf}, process, thread::{self, JoinHandle}, time::Duration};
+
+use aoc_2025::web::{Client, Config};
+
+// A stand-in puzzle server that answers one connection per response, in order, and returns the
+// requests it got.
+pub fn serve(responses : Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/2025", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut buf = [0; 1024];
+            while !complete(&request) {
+                let n = stream.read(&mut buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            requests.push(request);
+            write!(stream, "HTTP/1.0 {status} Whatever\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        }
+        requests
+    });
+    (base_url, server)
+}
+
+// Whether `request` has its headers and as much body as they say.
+fn complete(request : &str) -> bool {
+    let Some((head, body)) = request.split_once("\r\n\r\n") else {
+        return false;
+    };
+    let length = head.lines()
+        .find_map(|line| line.strip_prefix("Content-Length: "))
+        .map_or(0, |length| length.parse().unwrap());
+    body.len() >= length
+}
+
+// A fresh directory for the files of one test in `suite`.
+pub fn scratch(suite : &str, test : &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{suite}-{}-{test}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn client(base_url : &str, session : Option<&str>, throttle : Duration, dir : &Path) -> Client {
+    let config = Config { base_url: base_url.to_string(), session: session.map(str::to_string), throttle };
+    Client::new(config, dir.join("last-request"))
+}
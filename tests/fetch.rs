@@ -0,0 +1,60 @@
+use std::{fs, time::{Duration, Instant}};
+
+use common::{client, scratch, serve};
+
+mod common;
+
+#[test]
+fn downloads_then_uses_the_cache() {
+    let dir = scratch("fetch", "cache");
+    let (base_url, server) = serve(vec![(200, "1 2 3\n")]);
+    let client = client(&base_url, Some("secret"), Duration::ZERO, &dir);
+    let path = dir.join("inputs/day3.txt").display().to_string();
+
+    assert!(client.fetch_input(3, &path).unwrap());
+    assert!(!client.fetch_input(3, &path).unwrap());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /2025/day/3/input HTTP/1.0\r\n"), "{}", requests[0]);
+    assert!(requests[0].contains("\r\nCookie: session=secret\r\n"), "{}", requests[0]);
+}
+
+#[test]
+fn errors_are_not_cached() {
+    let dir = scratch("fetch", "errors");
+    let (base_url, server) = serve(vec![(404, "Not yet"), (400, "Log in")]);
+    let client = client(&base_url, Some("secret"), Duration::ZERO, &dir);
+    let path = dir.join("day9.txt").display().to_string();
+
+    for expected in ["isn't available yet", "rejected the session token"] {
+        let err = client.fetch_input(9, &path).unwrap_err();
+        assert_eq!(err.kind(), "http");
+        assert!(err.to_string().contains(expected), "{err}");
+        assert!(!fs::exists(&path).unwrap());
+    }
+    server.join().unwrap();
+}
+
+#[test]
+fn requests_are_throttled() {
+    let dir = scratch("fetch", "throttle");
+    let (base_url, server) = serve(vec![(200, "1\n"), (200, "2\n")]);
+    let throttle = Duration::from_millis(300);
+    let client = client(&base_url, Some("secret"), throttle, &dir);
+
+    let start = Instant::now();
+    client.fetch_input(1, &dir.join("day1.txt").display().to_string()).unwrap();
+    client.fetch_input(2, &dir.join("day2.txt").display().to_string()).unwrap();
+    assert!(start.elapsed() >= throttle);
+    server.join().unwrap();
+}
+
+#[test]
+fn needs_a_session_token() {
+    let dir = scratch("fetch", "session");
+    let client = client("http://127.0.0.1:9/2025", None, Duration::ZERO, &dir);
+    let err = client.fetch_input(1, &dir.join("day1.txt").display().to_string()).unwrap_err();
+    assert_eq!(err.kind(), "usage");
+}
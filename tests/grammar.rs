@@ -0,0 +1,41 @@
+use aoc_2025::{Part, registry};
+
+#[test]
+fn no_day_has_an_opaque_grammar() {
+    for day in registry().all() {
+        for part in Part::BOTH {
+            let grammar = day.grammar(part);
+            assert!(!grammar.has_opaque(), "day {} {part}:\n{}", day.number(), grammar.to_ebnf("input"));
+        }
+    }
+}
+
+#[test]
+fn day1_grammar() {
+    let day = registry().get(1).unwrap();
+    assert_eq!(day.grammar(Part::Easy).to_ebnf("input"), "\
+input      = Rotation, { whitespace, Rotation }, [ whitespace ] ;
+Rotation   = Direction, integer ;
+Direction  = \"L\" | \"R\" ;
+integer    = ( [ \"-\" ], ? token satisfying a predicate ?, { ? token satisfying a predicate ? } ) (* satisfying a predicate *) ;
+whitespace = { ? token satisfying a predicate ? } ;
+");
+}
+
+#[test]
+fn day5_grammar() {
+    let day = registry().get(5).unwrap();
+    assert_eq!(day.grammar(Part::Easy).to_ebnf("input"), "\
+input   = [ Range, { \"\\n\", Range } ], ? blank line ?, [ integer, { \"\\n\", integer } ] ;
+Range   = integer, \"-\", integer ;
+integer = ( [ \"-\" ], ? token satisfying a predicate ?, { ? token satisfying a predicate ? } ) (* satisfying a predicate *) ;
+");
+}
+
+#[test]
+fn railroad_diagrams_draw_every_rule() {
+    let svg = registry().get(5).unwrap().grammar(Part::Easy).to_railroad_svg("input");
+    for rule in ["input", "Range", "integer"] {
+        assert!(svg.contains(&format!(">{rule}</text>")), "{rule} missing from {svg}");
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_2025::{DynDay, Registry, day_modules, day1::Day1, day2::Day2, day4::Day4, registry};
+
+#[test]
+fn day_numbers_match_their_modules() {
+    for (module, day) in day_modules() {
+        assert_eq!(module, format!("day{}", day.number()));
+    }
+}
+
+#[test]
+fn registered_days_have_no_problems() {
+    assert_eq!(registry().problems(), Vec::<String>::new());
+    let numbers : Vec<i32> = registry().all().map(|day| day.number()).collect();
+    assert!(numbers.is_sorted(), "{numbers:?}");
+    assert_eq!(registry().puzzles().next().map(|day| day.number()), Some(1));
+}
+
+#[test]
+fn finds_duplicates_and_gaps() {
+    let days : Vec<&'static dyn DynDay> = vec![&Day4, &Day1, &Day1, &Day1, &Day4];
+    assert_eq!(Registry::new(days).problems(), [
+        "day 1 is registered more than once",
+        "day 4 is registered more than once",
+        "day 2 isn't registered",
+        "day 3 isn't registered",
+    ]);
+    assert_eq!(Registry::new(vec![&Day2]).problems(), ["day 1 isn't registered"]);
+    assert_eq!(Registry::new(vec![]).problems(), Vec::<String>::new());
+}
@@ -0,0 +1,27 @@
+use aoc_2025::{AocError, Part, registry};
+use parser_combinators::Rng;
+
+const SEEDS : u64 = 8;
+const MAX_SIZE : usize = 12;
+
+// Inputs sampled from a day's grammar must parse, and solving them may fail validation or be
+// refused by the solver, but must not panic.
+#[test]
+fn days_handle_sampled_inputs() {
+    for day in registry().puzzles() {
+        for seed in 0..SEEDS {
+            let mut rng = Rng::new(seed);
+            for size in 0..MAX_SIZE {
+                let Some(chars) = day.sample(&mut rng, size) else {
+                    continue;
+                };
+                let text : String = chars.iter().collect();
+                for (part, result) in day.solve_parts("sample", &chars, &Part::BOTH) {
+                    if let Err(e) = result {
+                        assert!(matches!(e, AocError::Validation(_) | AocError::Solver { panicked: false, .. }), "day {} {part} on {text:?}: {e}", day.number());
+                    }
+                }
+            }
+        }
+    }
+}
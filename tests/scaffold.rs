@@ -0,0 +1,76 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use aoc_2025::scaffold::new_day;
+use common::scratch;
+
+mod common;
+
+const LIB : &str = "pub mod cli;\n\ndays!(\n    day0::Day0,\n    day1::Day1,\n    day5::Day5,\n);\n\npub enum Part {}\n";
+
+// A crate root with the real day 0 template and a `lib.rs` registering days 0, 1 and 5.
+fn crate_root(test : &str) -> PathBuf {
+    let root = scratch("scaffold", test);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("inputs")).unwrap();
+    fs::copy("src/day0.rs", root.join("src/day0.rs")).unwrap();
+    fs::write(root.join("src/lib.rs"), LIB).unwrap();
+    root
+}
+
+fn read(root : &Path, path : &str) -> String {
+    fs::read_to_string(root.join(path)).unwrap()
+}
+
+#[test]
+fn creates_and_registers_a_day() {
+    let root = crate_root("create");
+    assert_eq!(new_day(&root, 3).unwrap(), ["src/day3.rs", "inputs/day3.txt", "inputs/day3_test.txt"]);
+    let code = read(&root, "src/day3.rs");
+    assert!(code.contains("pub struct Day3;") && code.contains("impl Day for Day3 {"), "{code}");
+    assert!(code.contains("fn day_number() -> i32 {\n        3\n"), "{code}");
+    assert!(code.contains("    Day3;\n    // TODO: fill in the example's answers.\n    example: file \"inputs/day3_test.txt\" => easy \"TODO\", hard \"TODO\";"), "{code}");
+    assert!(!code.contains("Day0") && !code.contains("day0"), "{code}");
+    assert_eq!(read(&root, "src/lib.rs"), LIB.replace("    day1::Day1,\n", "    day1::Day1,\n    day3::Day3,\n"));
+    assert_eq!(read(&root, "inputs/day3.txt"), "");
+    assert_eq!(read(&root, "inputs/day3_test.txt"), "");
+}
+
+#[test]
+fn registers_in_order() {
+    let root = crate_root("order");
+    new_day(&root, 9).unwrap();
+    new_day(&root, 2).unwrap();
+    let lib = read(&root, "src/lib.rs");
+    let days : Vec<&str> = lib.lines().filter(|line| line.starts_with("    day")).map(str::trim).collect();
+    assert_eq!(days, ["day0::Day0,", "day1::Day1,", "day2::Day2,", "day5::Day5,", "day9::Day9,"]);
+}
+
+#[test]
+fn refuses_days_already_registered() {
+    let root = crate_root("registered");
+    let err = new_day(&root, 5).unwrap_err();
+    assert_eq!(err.to_string(), "day 5 is already registered in src/lib.rs");
+    assert!(!root.join("src/day5.rs").exists() && !root.join("inputs/day5.txt").exists());
+    assert_eq!(read(&root, "src/lib.rs"), LIB);
+}
+
+#[test]
+fn refuses_to_overwrite() {
+    let root = crate_root("overwrite");
+    fs::write(root.join("inputs/day4_test.txt"), "mine").unwrap();
+    let err = new_day(&root, 4).unwrap_err();
+    assert_eq!(err.to_string(), "won't overwrite inputs/day4_test.txt");
+    assert_eq!(read(&root, "inputs/day4_test.txt"), "mine");
+    assert!(!root.join("src/day4.rs").exists() && !root.join("inputs/day4.txt").exists());
+    assert_eq!(read(&root, "src/lib.rs"), LIB);
+}
+
+#[test]
+fn refuses_day_0_and_changed_templates() {
+    let root = crate_root("template");
+    assert_eq!(new_day(&root, 0).unwrap_err().kind(), "usage");
+    fs::write(root.join("src/day0.rs"), read(&root, "src/day0.rs").replace("=> easy 6, hard 3;", "=> easy 7, hard 3;")).unwrap();
+    let err = new_day(&root, 6).unwrap_err();
+    assert_eq!(err.kind(), "validation", "{err}");
+    assert!(!root.join("src/day6.rs").exists());
+}
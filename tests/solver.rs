@@ -0,0 +1,61 @@
+use aoc_2025::{AocError, Day, Part, catch_solver, day3::Day3, parser_utils::{chr, digit, from_parse}};
+use parser_combinators::Parser;
+
+// Sums the numbers on each line, or in the hard part, their digits.
+struct Digits;
+
+impl Day for Digits {
+    type Input = Vec<u64>;
+    type Easy = u64;
+    type Hard = u64;
+
+    fn day_number() -> i32 {
+        99
+    }
+
+    fn parse_easy<'a>() -> impl Parser<'a, char, O=Self::Input> {
+        from_parse()
+    }
+
+    fn parse_hard<'a>() -> Option<impl Parser<'a, char, O=Self::Input>> {
+        Some(digit().map(|d| d.to_digit(10).unwrap() as u64).many1().sep_end_by1(chr('\n')).map(|lines| lines.concat()))
+    }
+
+    fn solve_easy(input : Self::Input) -> Result<u64, String> {
+        Ok(input.into_iter().sum())
+    }
+
+    fn solve_hard(input : Self::Input) -> Result<u64, String> {
+        Ok(input.into_iter().sum())
+    }
+}
+
+#[test]
+fn solvers_refuse_inputs_they_cannot_answer() {
+    let chars : Vec<char> = "987654321111111\n12345\n".chars().collect();
+    assert!(Day3::solve_part(Part::Easy, "banks", &chars).is_ok());
+    let err = Day3::solve_part(Part::Hard, "banks", &chars).unwrap_err();
+    assert!(matches!(&err, AocError::Solver { panicked: false, .. }), "{err}");
+    assert_eq!(err.kind(), "solver");
+    assert_eq!(err.to_string(), "solver failed: bank 2 has 5 batteries, but 12 are needed");
+}
+
+#[test]
+fn panics_are_caught_as_bugs() {
+    let err = catch_solver(|| -> u64 { panic!("oops") }).unwrap_err();
+    assert!(matches!(&err, AocError::Solver { panicked: true, .. }), "{err}");
+    assert_eq!(err.to_string(), "solver panicked: oops");
+}
+
+#[test]
+fn a_hard_parser_parses_separately() {
+    let chars : Vec<char> = "12\n34\n".chars().collect();
+    let solutions : Vec<(String, bool)> = Digits::solve_parts("digits", &chars, &Part::BOTH).into_iter()
+        .map(|(_, result)| result.map(|solution| (solution.answer, solution.reused_parse)).unwrap())
+        .collect();
+    assert_eq!(solutions, [("46".to_string(), false), ("10".to_string(), false)]);
+    let solutions : Vec<bool> = Day3::solve_parts("banks", &"987654321111111\n".chars().collect::<Vec<_>>(), &Part::BOTH).into_iter()
+        .map(|(_, result)| result.unwrap().reused_parse)
+        .collect();
+    assert_eq!(solutions, [false, true]);
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use aoc_2025::{Part, submit::{Attempts, Verdict, classify, message, submit}};
+use common::{client, scratch, serve};
+
+mod common;
+
+const TOO_HIGH : &str = "<main><article><p>That's not the right answer; your answer is too high.  \
+    Please wait one minute before trying again.</p></article></main>";
+const CORRECT : &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>";
+
+#[test]
+fn classifies_responses() {
+    let cases = [
+        (CORRECT, Some(Verdict::Correct)),
+        (TOO_HIGH, Some(Verdict::TooHigh)),
+        ("<article><p>That's not the right answer; your answer is too low.</p></article>", Some(Verdict::TooLow)),
+        ("<article><p>That's not the right answer.</p></article>", Some(Verdict::Wrong)),
+        ("<article><p>You gave an answer too recently; you have 42s left to wait.</p></article>", Some(Verdict::RateLimited)),
+        ("<article><p>You don't seem to be solving the right level.</p></article>", None),
+    ];
+    for (body, verdict) in cases {
+        assert_eq!(classify(body), verdict, "{body}");
+    }
+    assert_eq!(message(CORRECT), "That's the right answer! You are one gold star closer.");
+}
+
+#[test]
+fn posts_the_answer() {
+    let dir = scratch("submit", "post");
+    let (base_url, server) = serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+    let client = client(&base_url, Some("secret"), Duration::ZERO, &dir);
+
+    assert_eq!(submit(&client, 4, Part::Hard, "9001").unwrap().0, Verdict::TooHigh);
+    assert_eq!(submit(&client, 4, Part::Hard, "a b").unwrap().0, Verdict::Correct);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /2025/day/4/answer HTTP/1.0\r\n"), "{}", requests[0]);
+    assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=9001"), "{}", requests[0]);
+    assert!(requests[1].ends_with("\r\n\r\nlevel=2&answer=a%20b"), "{}", requests[1]);
+}
+
+#[test]
+fn vets_answers_against_earlier_attempts() {
+    let mut attempts = Attempts::default();
+    attempts.record(1, Part::Easy, Verdict::TooHigh, "100");
+    attempts.record(1, Part::Easy, Verdict::TooLow, "10");
+    attempts.record(1, Part::Easy, Verdict::RateLimited, "50");
+    attempts.record(1, Part::Easy, Verdict::Wrong, "42");
+
+    assert_eq!(attempts.vet(1, Part::Easy, "50", None).unwrap(), Vec::<String>::new());
+    assert_eq!(attempts.vet(1, Part::Easy, "42", None).unwrap_err().kind(), "wrong");
+    assert_eq!(attempts.vet(1, Part::Easy, "100", None).unwrap_err().kind(), "wrong");
+    assert_eq!(attempts.vet(1, Part::Easy, "150", None).unwrap().len(), 1);
+    assert_eq!(attempts.vet(1, Part::Easy, "5", None).unwrap().len(), 1);
+    // The other part and other days have their own bounds.
+    assert_eq!(attempts.vet(1, Part::Hard, "150", None).unwrap(), Vec::<String>::new());
+    assert_eq!(attempts.vet(2, Part::Easy, "42", None).unwrap(), Vec::<String>::new());
+
+    attempts.record(1, Part::Easy, Verdict::Correct, "50");
+    assert_eq!(attempts.vet(1, Part::Easy, "60", None).unwrap_err().kind(), "usage");
+}
+
+#[test]
+fn refuses_parts_with_an_accepted_answer() {
+    let attempts = Attempts::default();
+    for answer in ["1055", "1056"] {
+        let err = attempts.vet(1, Part::Easy, answer, Some("1055")).unwrap_err();
+        assert_eq!(err.kind(), "usage");
+        assert_eq!(err.to_string(), "day 1 part 1 is already solved: 1055 is the accepted answer");
+    }
+    assert_eq!(attempts.vet(1, Part::Hard, "1055", None).unwrap(), Vec::<String>::new());
+}
+
+#[test]
+fn attempts_survive_a_round_trip() {
+    let dir = scratch("submit", "round-trip");
+    let path = dir.join("attempts.txt").display().to_string();
+    let mut attempts = Attempts::default();
+    attempts.record(3, Part::Easy, Verdict::TooLow, "17");
+    attempts.record(3, Part::Hard, Verdict::Correct, "ABC,DEF");
+    attempts.save(&path).unwrap();
+
+    let loaded = Attempts::load(&path).unwrap();
+    let loaded : Vec<_> = loaded.for_part(3, Part::Easy).chain(loaded.for_part(3, Part::Hard)).cloned().collect();
+    let saved : Vec<_> = attempts.for_part(3, Part::Easy).chain(attempts.for_part(3, Part::Hard)).cloned().collect();
+    assert_eq!(loaded, saved);
+}
@@ -0,0 +1,33 @@
+use std::{sync::Arc, time::Duration};
+
+use aoc_2025::{Answers, AocError, Part, Solution, default_input, summary::{Row, Status, json, table}};
+
+fn row(day : i32, result : Result<&str, AocError>) -> Row {
+    let result = result.map(|answer| Solution {
+        answer: answer.to_string(),
+        parse_time: Duration::ZERO,
+        solve_time: Duration::ZERO,
+        reused_parse: false,
+    });
+    Row { day, part: Part::Easy, input_times: None, result: result.map_err(Arc::new) }
+}
+
+#[test]
+fn status_compares_with_the_accepted_answer() {
+    let mut answers = Answers::default();
+    answers.set(1, &default_input(1), Part::Easy, "42".to_string());
+    let rows = [
+        row(1, Ok("42")),
+        row(1, Ok("41")),
+        row(2, Ok("7")),
+        row(1, Err(AocError::Validation("no".to_string()))),
+    ];
+    let statuses : Vec<Status> = rows.iter().map(|row| row.status(&answers)).collect();
+    assert_eq!(statuses, [Status::Correct, Status::Wrong { expected: "42" }, Status::Unchecked, Status::Failed("validation")]);
+    let names : Vec<&str> = statuses.iter().map(|status| status.name()).collect();
+    assert_eq!(names, ["correct", "wrong", "ok", "validation"]);
+
+    let table = table(&rows, &answers);
+    assert!(table.contains("✗ (expected 42)"), "{table}");
+    assert!(json(&rows, &answers).contains("\"status\": \"wrong\""));
+}
@@ -0,0 +1,102 @@
+use aoc_2025::{day1::rotations, day2::ranges, day4::paper_grid, day5::inventory, grid::Grid, parser_utils::{nat, parse_as}, range::Range};
+use parser_combinators::{Parser, assert_round_trip, check::{check, naturals, vecs}, print_to_vec};
+
+fn text(chars : &[char]) -> String {
+    chars.iter().collect()
+}
+
+#[test]
+fn rotations_round_trips() {
+    check("rotations round trip", vecs((naturals(1), naturals(999))), |turns| {
+        if turns.is_empty() {
+            return true;
+        }
+        let input : String = turns.iter()
+            .map(|(dir, amount)| format!("{}{amount}\n", if *dir == 0 { 'L' } else { 'R' }))
+            .collect();
+        let chars : Vec<char> = input.chars().collect();
+        let syntax = rotations();
+        let Ok((parsed, [])) = syntax.parse(&chars) else {
+            return false;
+        };
+        let mut buf = Vec::new();
+        assert_round_trip(&rotations(), &parsed, &mut buf);
+        print_to_vec(&syntax, &parsed).is_some_and(|printed| text(&printed) == input)
+    });
+}
+
+#[test]
+fn ranges_round_trips() {
+    check("ranges round trip", vecs((naturals(1 << 40), naturals(1 << 40))), |pairs| {
+        if pairs.is_empty() {
+            return true;
+        }
+        let values : Vec<Range> = pairs.iter().map(|(lo, hi)| Range(*lo, *hi)).collect();
+        let mut buf = Vec::new();
+        assert_round_trip(&ranges(), &values, &mut buf);
+        text(&buf) == pairs.iter().map(|(lo, hi)| format!("{lo}-{hi}")).collect::<Vec<_>>().join(",") + "\n"
+    });
+}
+
+#[test]
+fn inventory_round_trips() {
+    let strategy = (vecs((naturals(1000), naturals(1000))), vecs(naturals(1000)));
+    check("inventory round trip", strategy, |(pairs, ids)| {
+        if pairs.is_empty() || ids.is_empty() {
+            return true;
+        }
+        let value = (pairs.iter().map(|(lo, hi)| Range(*lo, *hi)).collect(), ids.clone());
+        let mut buf = Vec::new();
+        assert_round_trip(&inventory(), &value, &mut buf);
+        true
+    });
+}
+
+#[test]
+fn paper_grid_round_trips() {
+    check("paper grid round trip", (naturals(9), vecs(naturals(1))), |(width, cells)| {
+        let width = *width as usize + 1;
+        let rows : Vec<Vec<bool>> = cells.chunks_exact(width).map(|row| row.iter().map(|c| *c == 1).collect()).collect();
+        if rows.is_empty() {
+            return true;
+        }
+        let grid = Grid::new(rows.clone());
+        let mut buf = Vec::new();
+        assert_round_trip(&paper_grid(), &grid, &mut buf);
+        let expected : String = rows.iter()
+            .map(|row| row.iter().map(|c| if *c { '@' } else { '.' }).chain(['\n']).collect::<String>())
+            .collect();
+        text(&buf) == expected
+    });
+}
+
+#[test]
+fn ragged_grids_are_rejected() {
+    let chars : Vec<char> = "..@\n.@\n".chars().collect();
+    assert!(paper_grid().parse(&chars).is_err());
+}
+
+#[test]
+fn nat_rejects_numbers_out_of_range() {
+    let chars : Vec<char> = "255 256".chars().collect();
+    assert_eq!(nat::<u8>().parse(&chars).map(|(n, rest)| (n, rest.len())).ok(), Some((255, 4)));
+    assert!(nat::<u8>().parse(&chars[4..]).is_err());
+    assert_eq!(print_to_vec(&nat::<u8>(), &255).map(|printed| text(&printed)).as_deref(), Some("255"));
+}
+
+#[test]
+fn rotations_are_unsigned() {
+    for input in ["L-5\n", "R4294967296\n"] {
+        let chars : Vec<char> = input.chars().collect();
+        assert!(!matches!(rotations().parse(&chars), Ok((_, []))), "{input:?}");
+    }
+}
+
+#[test]
+fn parse_as_reports_errors() {
+    assert_eq!(parse_as::<Vec<u64>>("numbers", "1\n2\n").unwrap(), [1, 2]);
+    assert_eq!(parse_as::<Vec<u64>>("numbers", "").unwrap(), Vec::<u64>::new());
+    let err = parse_as::<Vec<u64>>("numbers", "1\n2x\n").unwrap_err();
+    assert_eq!(err.kind(), "parse");
+    assert!(err.to_string().contains("numbers:2:2"), "{err}");
+}